@@ -0,0 +1,8 @@
+aoc::examples! {
+    part1_input0: part1(include_str!("input0.txt")) => 3,
+    part1_balanced: part1("(())") => 0,
+    part1_negative: part1(")())())") => -3,
+    part2_input0: part2(include_str!("input0.txt")) => None,
+    part2_first: part2(")") => Some(1),
+    part2_later: part2("()())") => Some(5),
+}
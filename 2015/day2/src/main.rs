@@ -1,5 +1,11 @@
-use std::{cmp, iter, str};
+#[cfg(test)]
+mod examples;
+#[cfg(test)]
+mod properties;
 
+use std::{cmp, fmt, iter, str};
+
+#[derive(Debug, PartialEq, Eq)]
 struct Cuboid {
     l: u32,
     w: u32,
@@ -47,10 +53,20 @@ impl str::FromStr for Cuboid {
     }
 }
 
+impl fmt::Display for Cuboid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{}x{}", self.l, self.w, self.h)
+    }
+}
+
 fn main() {
     let input = include_str!("input1.txt");
-    println!("{}", part1(input).unwrap_or(0));
-    println!("{}", part2(input).unwrap_or(0));
+
+    aoc::run::Day::new("2015/day2")
+        .input("input1", input)
+        .part(1, part1)
+        .part(2, part2)
+        .run();
 }
 
 fn part1(s: &str) -> Option<u32> {
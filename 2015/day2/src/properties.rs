@@ -0,0 +1,31 @@
+use aoc::{generate::Rng, prop};
+
+use crate::Cuboid;
+
+fn cuboid(rng: &mut Rng) -> Cuboid {
+    Cuboid {
+        l: rng.range(1..100) as u32,
+        w: rng.range(1..100) as u32,
+        h: rng.range(1..100) as u32,
+    }
+}
+
+#[test]
+fn cuboids_round_trip() {
+    prop::check(cuboid, |c| c.to_string().parse().as_ref() == Ok(c));
+}
+
+#[test]
+fn corrupted_cuboids_are_rejected() {
+    let corrupted = |rng: &mut Rng| {
+        let line = cuboid(rng).to_string();
+        prop::corrupt(rng, &line)
+    };
+
+    // a deleted or doubled digit is still a cuboid
+    prop::check(corrupted, |line| {
+        line.parse::<Cuboid>()
+            .ok()
+            .is_none_or(|c| c.to_string() == *line)
+    });
+}
@@ -0,0 +1,171 @@
+//! any number of agents delivering along the same list of moves. they all start at the origin and
+//! deliver there, then each one delivers wherever its moves take it
+
+use std::{collections::HashMap, fmt, str::FromStr};
+
+pub type House = (i32, i32);
+
+/// which agent makes which move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+    /// one move each in turn, like Santa and Robo-Santa
+    RoundRobin,
+    /// the moves cut into one run per agent, the first agent taking the first run
+    Chunked,
+}
+
+impl FromStr for Policy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "round-robin" => Ok(Policy::RoundRobin),
+            "chunked" => Ok(Policy::Chunked),
+            _ => Err(format!("unknown policy {s}, try round-robin or chunked")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delivery {
+    /// how many presents each house got
+    visits: HashMap<House, usize>,
+}
+
+impl Delivery {
+    pub fn new(moves: &[House], agents: usize, policy: Policy) -> Self {
+        let mut visits = HashMap::new();
+
+        if agents > 0 {
+            visits.insert((0, 0), agents);
+        }
+
+        let chunk = moves.len().div_ceil(agents.max(1)).max(1);
+        let mut positions = vec![(0, 0); agents];
+
+        for (i, (dx, dy)) in moves.iter().enumerate().filter(|_| agents > 0) {
+            let agent = match policy {
+                Policy::RoundRobin => i % agents,
+                Policy::Chunked => i / chunk,
+            };
+            let pos = &mut positions[agent];
+            *pos = (pos.0 + dx, pos.1 + dy);
+
+            *visits.entry(*pos).or_default() += 1;
+        }
+
+        Delivery { visits }
+    }
+
+    /// how many houses got at least one present
+    pub fn houses(&self) -> usize {
+        self.visits.len()
+    }
+
+    /// how many presents each house got
+    pub fn visits(&self) -> &HashMap<House, usize> {
+        &self.visits
+    }
+
+    /// the house that got the most presents, the lowest one first on a tie
+    pub fn most_visited(&self) -> Option<(House, usize)> {
+        self.visits
+            .iter()
+            .max_by_key(|&(&(x, y), &visits)| (visits, -y, -x))
+            .map(|(&house, &visits)| (house, visits))
+    }
+
+    /// the lowest and highest corners of the houses that got a present
+    pub fn bounds(&self) -> Option<(House, House)> {
+        let xs = self.visits.keys().map(|h| h.0);
+        let ys = self.visits.keys().map(|h| h.1);
+
+        Some((
+            (xs.clone().min()?, ys.clone().min()?),
+            (xs.max()?, ys.max()?),
+        ))
+    }
+
+    /// how many houses got a given number of presents, by number of presents
+    pub fn histogram(&self) -> Vec<(usize, usize)> {
+        let mut counts = HashMap::<usize, usize>::new();
+        for &visits in self.visits().values() {
+            *counts.entry(visits).or_default() += 1;
+        }
+
+        let mut histogram = counts.into_iter().collect::<Vec<_>>();
+        histogram.sort_unstable();
+        histogram
+    }
+}
+
+impl fmt::Display for Delivery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "houses: {}", self.houses())?;
+
+        if let Some(((x, y), visits)) = self.most_visited() {
+            writeln!(f, "most visited: {x},{y} with {visits}")?;
+        }
+        if let Some(((x0, y0), (x1, y1))) = self.bounds() {
+            writeln!(f, "bounds: {x0},{y0} to {x1},{y1}")?;
+        }
+        for (visits, houses) in self.histogram() {
+            writeln!(f, "{houses} houses got {visits}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UP: House = (0, 1);
+    const DOWN: House = (0, -1);
+    const RIGHT: House = (1, 0);
+
+    #[test]
+    fn agents_share_the_moves_by_policy() {
+        let moves = [UP, UP, UP, RIGHT, RIGHT, RIGHT];
+
+        // each agent goes up then right
+        let delivery = Delivery::new(&moves, 3, Policy::RoundRobin);
+        assert_eq!(delivery.houses(), 3);
+        assert_eq!(delivery.visits()[&(0, 0)], 3);
+        assert_eq!(delivery.visits()[&(1, 1)], 3);
+
+        // two moves each: up up, up right, and right right
+        let delivery = Delivery::new(&moves, 3, Policy::Chunked);
+        assert_eq!(delivery.houses(), 6);
+        assert_eq!(delivery.visits()[&(0, 1)], 2);
+        assert_eq!(delivery.visits()[&(2, 0)], 1);
+        assert_eq!(delivery.most_visited(), Some(((0, 0), 3)));
+        assert_eq!(delivery.bounds(), Some(((0, 0), (2, 2))));
+    }
+
+    #[test]
+    fn chunks_cover_every_move() {
+        let moves = [UP, DOWN, UP, DOWN, UP];
+
+        for agents in 1..8 {
+            let delivery = Delivery::new(&moves, agents, Policy::Chunked);
+            let presents = delivery
+                .histogram()
+                .iter()
+                .map(|(v, h)| v * h)
+                .sum::<usize>();
+
+            assert_eq!(presents, agents + moves.len(), "{agents} agents");
+        }
+    }
+
+    #[test]
+    fn nobody_delivers_nothing() {
+        let delivery = Delivery::new(&[UP], 0, Policy::RoundRobin);
+
+        assert_eq!(delivery.houses(), 0);
+        assert_eq!(delivery.most_visited(), None);
+        assert_eq!(delivery.bounds(), None);
+    }
+}
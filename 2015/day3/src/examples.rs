@@ -0,0 +1,7 @@
+aoc::examples! {
+    part1_input0: part1(include_str!("input0.txt")) => 4,
+    part1_back_and_forth: part1("^v^v^v^v^v") => 2,
+    part2_input0: part2(include_str!("input0.txt")) => 3,
+    part2_back_and_forth: part2("^v^v^v^v^v") => 11,
+    part2_not_moves: part2("^?v\n^v\n") => 5,
+}
@@ -1,52 +1,53 @@
-use std::collections::HashSet;
+mod delivery;
+#[cfg(test)]
+mod examples;
+
+use delivery::{Delivery, House, Policy};
 
 fn main() {
     let input = include_str!("input1.txt");
-    println!("{:?}", part1(input));
-    println!("{:?}", part2(input));
-}
 
-fn part1(s: &str) -> usize {
-    let mut pos = (0, 0);
-    let mut houses: HashSet::<(i32, i32)> = HashSet::from([pos]);
+    // e.g. `--agents 5 --policy chunked`
+    if let Some(agents) = aoc::args::after("--agents") {
+        let agents = agents.parse().expect("invalid number of agents");
+        let policy = aoc::args::after("--policy").map_or(Policy::RoundRobin, |policy| {
+            policy.parse().unwrap_or_else(|e| panic!("{e}"))
+        });
 
-    for c in s.chars() {
-        match c {
-            '>' => pos.0 += 1,
-            '^' => pos.1 += 1,
-            '<' => pos.0 -= 1,
-            'v' => pos.1 -= 1,
-            _ => (),
-        }
-
-        houses.insert(pos);
+        print!("{}", Delivery::new(&parse_input(input), agents, policy));
+        return;
     }
 
-    houses.len()
+    aoc::run::Day::new("2015/day3")
+        .input("input1", input)
+        .parse("parse", parse_input)
+        .part(1, part1)
+        .part(2, part2)
+        .run();
 }
 
-fn part2(s: &str) -> usize {
-    let mut santa_pos = (0, 0);
-    let mut robo_pos = (0, 0);
-    let mut current_pos = &mut santa_pos;
-    let mut houses: HashSet::<(i32, i32)> = HashSet::from([*current_pos]);
-
-    for (index, c) in s.chars().enumerate() {
-        current_pos = match index % 2 {
-            0 => &mut santa_pos,
-            _ => &mut robo_pos,
-        };
-
-        match c {
-            '>' => current_pos.0 += 1,
-            '^' => current_pos.1 += 1,
-            '<' => current_pos.0 -= 1,
-            'v' => current_pos.1 -= 1,
-            _ => (),
-        }
-
-        houses.insert(*current_pos);
-    }
+/// the moves in the input. line breaks aren't moves, and anything else that isn't is logged
+fn parse_input(input: &str) -> Vec<House> {
+    input
+        .chars()
+        .filter_map(|c| match c {
+            '>' => Some((1, 0)),
+            '^' => Some((0, 1)),
+            '<' => Some((-1, 0)),
+            'v' => Some((0, -1)),
+            c if c.is_whitespace() => None,
+            c => {
+                aoc::warn!("ignoring {c:?}, it isn't a move");
+                None
+            }
+        })
+        .collect()
+}
+
+fn part1(input: &str) -> usize {
+    Delivery::new(&parse_input(input), 1, Policy::RoundRobin).houses()
+}
 
-    houses.len()
+fn part2(input: &str) -> usize {
+    Delivery::new(&parse_input(input), 2, Policy::RoundRobin).houses()
 }
@@ -0,0 +1,4 @@
+aoc::examples! {
+    part1_abcdef: part1("abcdef") => Some(609043),
+    part1_pqrstuv: part1("pqrstuv") => Some(1048970),
+}
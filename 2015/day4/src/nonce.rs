@@ -0,0 +1,103 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    thread,
+};
+
+use md5::{Digest, Hasher};
+
+/// nonces each worker claims at a time
+const CHUNK_SIZE: u64 = 4096;
+
+/// nonces hashed together by `finalize_lanes`. `CHUNK_SIZE` must be a multiple of it
+const LANES: usize = 8;
+
+/// enough for `u64::MAX`
+const MAX_DIGITS: usize = 20;
+
+/// writes `n` in base 10 at the end of `buf`, and returns where it starts
+fn write_decimal(mut n: u64, buf: &mut [u8; MAX_DIGITS]) -> usize {
+    let mut pos = buf.len();
+
+    loop {
+        pos -= 1;
+        buf[pos] = b'0' + (n % 10) as u8;
+        n /= 10;
+
+        if n == 0 {
+            break;
+        }
+    }
+
+    pos
+}
+
+/// looks for the lowest `n` such that `md5(secret + n)` satisfies a predicate.
+/// the secret is hashed only once, and every nonce starts from that state
+pub struct NonceSearch {
+    prefix: Hasher,
+    threads: usize,
+}
+
+impl NonceSearch {
+    pub fn new(secret: &str) -> Self {
+        let mut prefix = Hasher::new();
+        prefix.update(secret);
+
+        NonceSearch {
+            prefix,
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+        }
+    }
+
+    /// the nonces are split in chunks, claimed in increasing order by the workers.
+    /// once a winner is known, chunks past it are not worth scanning, but the ones before
+    /// still are, since they could hold a lower winner
+    pub fn find<F>(&self, accept: F) -> Option<u64>
+    where
+        F: Fn(&Digest) -> bool + Sync,
+    {
+        let next_chunk = AtomicU64::new(0);
+        let best = AtomicU64::new(u64::MAX);
+
+        thread::scope(|s| {
+            for _ in 0..self.threads {
+                s.spawn(|| self.worker(&accept, &next_chunk, &best));
+            }
+        });
+
+        match best.into_inner() {
+            u64::MAX => None,
+            n => Some(n),
+        }
+    }
+
+    fn worker<F>(&self, accept: &F, next_chunk: &AtomicU64, best: &AtomicU64)
+    where
+        F: Fn(&Digest) -> bool,
+    {
+        let mut bufs = [[0; MAX_DIGITS]; LANES];
+        let mut starts = [0; LANES];
+
+        loop {
+            let start = next_chunk.fetch_add(CHUNK_SIZE, Ordering::Relaxed);
+            if start >= best.load(Ordering::Relaxed) || start > u64::MAX - CHUNK_SIZE {
+                return;
+            }
+
+            for first in (start..start + CHUNK_SIZE).step_by(LANES) {
+                for (l, (buf, start)) in bufs.iter_mut().zip(&mut starts).enumerate() {
+                    *start = write_decimal(first + l as u64, buf);
+                }
+
+                let digests = self
+                    .prefix
+                    .finalize_lanes::<LANES>(std::array::from_fn(|l| &bufs[l][starts[l]..]));
+
+                if let Some(l) = digests.iter().position(&accept) {
+                    best.fetch_min(first + l as u64, Ordering::Relaxed);
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,11 @@
+const PART2_EXAMPLE: &str = "\
+qjhvhtzxzqqjkmpb
+xxyxx
+uurcxstgmygtbstg
+ieodomkazucvgmuy
+";
+
+aoc::examples! {
+    part1_input0: part1(include_str!("input0.txt")) => 2,
+    part2_example: part2(PART2_EXAMPLE) => 2,
+}
@@ -1,7 +1,14 @@
+#[cfg(test)]
+mod examples;
+
 fn main() {
     let input = include_str!("input1.txt");
-    println!("{:?}", part1(input));
-    println!("{:?}", part2(input));
+
+    aoc::run::Day::new("2015/day5")
+        .input("input1", input)
+        .part(1, part1)
+        .part(2, part2)
+        .run();
 }
 
 fn part1(input: &str) -> u32 {
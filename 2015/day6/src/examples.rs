@@ -0,0 +1,7 @@
+aoc::examples! {
+    part1_input0: part1(include_str!("input0.txt")) => Some(1_000_000 - 1000 - 4),
+    part1_past_1000: part1("turn on 0,0 through 1999,0\ntoggle 1000,0 through 1000,4") => Some(2003),
+    part2_input0: part2(include_str!("input0.txt")) => Some(1_000_000 + 2000 - 4),
+    part2_single: part2("turn on 0,0 through 0,0") => Some(1),
+    part2_toggle: part2("toggle 0,0 through 999,999") => Some(2_000_000),
+}
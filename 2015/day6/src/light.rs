@@ -0,0 +1,222 @@
+//! what the instructions mean for a light. an [`Effect`] is what some instructions do to a light
+//! one after the other, which is all [`grid`](crate::grid) and [`sweep`](crate::sweep) need: on
+//! and off for part 1, a brightness for part 2, or anything else that composes, like the colours
+//! and counters in the tests
+
+use crate::Op;
+
+/// what's summed over the lights, e.g. `u64` or an array of channels
+pub trait Total: Copy {
+    const ZERO: Self;
+
+    /// adds `value` for each of `count` lights
+    fn add(&mut self, value: Self, count: u64);
+}
+
+impl Total for u64 {
+    const ZERO: Self = 0;
+
+    fn add(&mut self, value: Self, count: u64) {
+        *self += value * count;
+    }
+}
+
+impl<const N: usize> Total for [u64; N] {
+    const ZERO: Self = [0; N];
+
+    fn add(&mut self, value: Self, count: u64) {
+        for (total, value) in self.iter_mut().zip(value) {
+            *total += value * count;
+        }
+    }
+}
+
+/// what instructions do to a light, composed in order
+pub trait Effect: Copy {
+    type Value: Total;
+
+    /// what no instruction does
+    const NONE: Self;
+
+    fn of(op: Op) -> Self;
+
+    /// `self` and then `next`
+    fn then(self, next: Self) -> Self;
+
+    /// what a light that starts off ends up at
+    fn value(self) -> Self::Value;
+}
+
+/// part 1: `light & keep ^ flip`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lit {
+    keep: bool,
+    flip: bool,
+}
+
+impl Effect for Lit {
+    type Value = u64;
+
+    const NONE: Self = Lit {
+        keep: true,
+        flip: false,
+    };
+
+    fn of(op: Op) -> Self {
+        match op {
+            Op::On => Lit {
+                keep: false,
+                flip: true,
+            },
+            Op::Off => Lit {
+                keep: false,
+                flip: false,
+            },
+            Op::Toggle => Lit {
+                keep: true,
+                flip: true,
+            },
+        }
+    }
+
+    fn then(self, next: Self) -> Self {
+        Lit {
+            keep: self.keep & next.keep,
+            flip: self.flip & next.keep ^ next.flip,
+        }
+    }
+
+    fn value(self) -> u64 {
+        self.flip as u64
+    }
+}
+
+/// part 2: `max(brightness + add, floor)`, since turning off stops at 0
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Brightness {
+    add: i64,
+    floor: i64,
+}
+
+impl Effect for Brightness {
+    type Value = u64;
+
+    const NONE: Self = Brightness {
+        add: 0,
+        floor: i64::MIN,
+    };
+
+    fn of(op: Op) -> Self {
+        match op {
+            Op::On => Brightness {
+                add: 1,
+                ..Self::NONE
+            },
+            Op::Off => Brightness { add: -1, floor: 0 },
+            Op::Toggle => Brightness {
+                add: 2,
+                ..Self::NONE
+            },
+        }
+    }
+
+    fn then(self, next: Self) -> Self {
+        Brightness {
+            add: self.add + next.add,
+            floor: self.floor.saturating_add(next.add).max(next.floor),
+        }
+    }
+
+    fn value(self) -> u64 {
+        self.add.max(self.floor) as u64
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    /// on, off and toggle go up by 1, 2 and 3, and wrap around at 5
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Counter(u64);
+
+    impl Effect for Counter {
+        type Value = u64;
+
+        const NONE: Self = Counter(0);
+
+        fn of(op: Op) -> Self {
+            Counter(op as u64 + 1)
+        }
+
+        fn then(self, next: Self) -> Self {
+            Counter((self.0 + next.0) % 5)
+        }
+
+        fn value(self) -> u64 {
+            self.0
+        }
+    }
+
+    /// on sets the red channel, toggle flips the green one, and off clears both but leaves the
+    /// blue one counting how many times it happened
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Colour {
+        red: Lit,
+        green: Lit,
+        offs: u64,
+    }
+
+    impl Effect for Colour {
+        type Value = [u64; 3];
+
+        const NONE: Self = Colour {
+            red: Lit::NONE,
+            green: Lit::NONE,
+            offs: 0,
+        };
+
+        fn of(op: Op) -> Self {
+            match op {
+                Op::On => Colour {
+                    red: Lit::of(Op::On),
+                    ..Self::NONE
+                },
+                Op::Off => Colour {
+                    red: Lit::of(Op::Off),
+                    green: Lit::of(Op::Off),
+                    offs: 1,
+                },
+                Op::Toggle => Colour {
+                    green: Lit::of(Op::Toggle),
+                    ..Self::NONE
+                },
+            }
+        }
+
+        fn then(self, next: Self) -> Self {
+            Colour {
+                red: self.red.then(next.red),
+                green: self.green.then(next.green),
+                offs: self.offs + next.offs,
+            }
+        }
+
+        fn value(self) -> [u64; 3] {
+            [self.red.value(), self.green.value(), self.offs]
+        }
+    }
+
+    #[test]
+    fn effects_compose_in_order() {
+        let apply = |ops: &[Op]| {
+            ops.iter()
+                .fold(Brightness::NONE, |b, &op| b.then(Brightness::of(op)))
+        };
+
+        assert_eq!(apply(&[Op::Off, Op::On]).value(), 1);
+        assert_eq!(apply(&[Op::On, Op::Off, Op::Off, Op::Toggle]).value(), 2);
+        assert_eq!(Lit::of(Op::On).then(Lit::of(Op::Toggle)).value(), 0);
+        assert_eq!(Lit::of(Op::Toggle).then(Lit::of(Op::On)).value(), 1);
+    }
+}
@@ -1,4 +1,19 @@
-#[derive(Clone, Copy, Debug)]
+#[cfg(test)]
+mod examples;
+mod light;
+#[cfg(test)]
+mod properties;
+mod sweep;
+
+use std::fmt;
+
+use aoc::{
+    bitgrid::BitGrid,
+    image::{Image, heatmap},
+};
+use light::{Brightness, Effect, Lit};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum Op {
     On,
     Off,
@@ -8,21 +23,33 @@ enum Op {
 type Range = (usize, usize);
 type Instruction = (Op, Range, Range);
 
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Op::On => "turn on",
+            Op::Off => "turn off",
+            Op::Toggle => "toggle",
+        })
+    }
+}
+
+/// coordinates fit in a `u32`, so that one past them fits in a `usize` for the sweep
 fn parse_range(line: &str) -> Option<Range> {
-    let (w, h) = line.trim().split_once(',')?;
+    let (w, h) = line.split_once(',')?;
+    let coordinate = |s: &str| s.parse::<u32>().ok().map(|n| n as usize);
 
-    Some((w.parse().ok()?, h.parse().ok()?))
+    Some((coordinate(w)?, coordinate(h)?))
 }
 
 fn parse_op(line: &str) -> Option<(&str, Op)> {
     let table = [
-        ("turn on", Op::On),
-        ("turn off", Op::Off),
-        ("toggle", Op::Toggle),
+        ("turn on ", Op::On),
+        ("turn off ", Op::Off),
+        ("toggle ", Op::Toggle),
     ];
 
     table.iter().find_map(|t| {
-        let leftover = line.trim().strip_prefix(t.0)?;
+        let leftover = line.strip_prefix(t.0)?;
         Some((leftover, t.1))
     })
 }
@@ -31,7 +58,7 @@ fn parse_input(input: &str) -> Option<Vec<Instruction>> {
     input
         .lines()
         .map(|line| {
-            let (left, right) = line.split_once("through")?;
+            let (left, right) = line.split_once(" through ")?;
             let (left, op) = parse_op(left)?;
             let beg = parse_range(left)?;
             let end = parse_range(right)?;
@@ -41,57 +68,121 @@ fn parse_input(input: &str) -> Option<Vec<Instruction>> {
         .collect()
 }
 
-fn part1(input: &str) -> Option<u32> {
-    let mut lights = vec![[false; 1000]; 1000];
+/// what `parse_input` reads back
+#[cfg(test)]
+fn format_input(instructions: &[Instruction]) -> String {
+    instructions
+        .iter()
+        .map(|(op, (bx, by), (ex, ey))| format!("{op} {bx},{by} through {ex},{ey}\n"))
+        .collect()
+}
+
+const SIZE: usize = 1000;
+
+/// every light of a `SIZE` grid, for drawing it. `None` if an instruction goes past it
+fn grid<E: Effect>(input: &str) -> Option<Vec<[E; SIZE]>> {
+    let mut lights = vec![[E::NONE; SIZE]; SIZE];
     let instructions = parse_input(input)?;
 
-    for i in instructions {
-        let (op, (bx, by), (ex, ey)) = i;
+    for (op, (bx, by), (ex, ey)) in instructions {
         for x in bx..=ex {
-            let row = &mut lights.get_mut(x)?[by..=ey];
-            match op {
-                Op::On => row.fill(true),
-                Op::Off => row.fill(false),
-                Op::Toggle => row.iter_mut().for_each(|b| *b = !*b),
-            }
+            let row = lights.get_mut(x)?.get_mut(by..=ey)?;
+            row.iter_mut()
+                .for_each(|light| *light = light.then(E::of(op)));
         }
     }
 
-    Some(
-        lights
-            .iter()
-            .map(|rows| rows.iter().map(|b| *b as u32).sum::<u32>())
-            .sum(),
-    )
+    Some(lights)
 }
 
-fn part2(input: &str) -> Option<u32> {
-    let mut lights = vec![[0u32; 1000]; 1000];
-    let instructions = parse_input(input)?;
+/// the lights of part 1 on a `SIZE` grid, a row at a time, for drawing them. rows go along the
+/// first coordinate
+fn lights(input: &str) -> Option<BitGrid> {
+    let mut lights = BitGrid::new(SIZE, SIZE);
+
+    for (op, (bx, by), (ex, ey)) in parse_input(input)? {
+        if ex >= SIZE || ey >= SIZE {
+            return None;
+        }
 
-    for i in instructions {
-        let (op, (bx, by), (ex, ey)) = i;
         for x in bx..=ex {
-            let row = &mut lights.get_mut(x)?[by..=ey];
-            let op_fn: fn(&mut u32) = match op {
-                Op::On => |b| *b += 1,
-                Op::Off => |b| *b = b.saturating_sub(1),
-                Op::Toggle => |b| *b += 2,
-            };
-            row.iter_mut().for_each(op_fn);
+            match op {
+                Op::On => lights.fill_row(x, by..ey + 1, true),
+                Op::Off => lights.fill_row(x, by..ey + 1, false),
+                Op::Toggle => lights.toggle_row(x, by..ey + 1),
+            }
         }
     }
 
-    Some(lights.iter().map(|rows| rows.iter().sum::<u32>()).sum())
+    Some(lights)
+}
+
+fn part1(input: &str) -> Option<u64> {
+    Some(sweep::total::<Lit>(&parse_input(input)?))
+}
+
+fn part2(input: &str) -> Option<u64> {
+    Some(sweep::total::<Brightness>(&parse_input(input)?))
+}
+
+/// the first coordinate goes right
+fn lights_image(input: &str) -> Option<Image> {
+    let lights = lights(input)?;
+
+    Some(Image::from_fn(SIZE, SIZE, |x, y| {
+        if lights.get(y, x) { [255; 3] } else { [0; 3] }
+    }))
+}
+
+fn brightness_image(input: &str) -> Option<Image> {
+    let brightness = grid::<Brightness>(input)?;
+    let max = brightness.iter().flatten().map(|b| b.value()).max();
+    let max = max.map_or(1, |m| m.max(1)) as f64;
+
+    Some(Image::from_fn(SIZE, SIZE, |x, y| {
+        heatmap(brightness[x][y].value() as f64 / max)
+    }))
 }
 
 fn main() {
     let input0 = include_str!("input0.txt");
     let input1 = include_str!("input1.txt");
 
-    println!("{:?}", part1(input0));
-    println!("{:?}", part1(input1));
+    if let Some(dir) = aoc::image::dir_from_args() {
+        let lights = lights_image(input1).expect("invalid instructions");
+        lights.save(dir.join("lights.png")).unwrap();
+
+        let brightness = brightness_image(input1).expect("invalid instructions");
+        brightness.save(dir.join("brightness.png")).unwrap();
+        return;
+    }
+
+    // e.g. `--area "0,0 through 499,499" --after 10`
+    if let Some(area) = aoc::args::after("--area") {
+        let instructions = parse_input(input1).expect("invalid instructions");
+        let (from, to) = area.split_once(" through ").expect("invalid area");
+        let area = (
+            parse_range(from).expect("invalid area"),
+            parse_range(to).expect("invalid area"),
+        );
+        let after = aoc::args::after("--after").map_or(instructions.len(), |n| {
+            n.parse().expect("invalid number of instructions")
+        });
+
+        let instructions = &instructions[..after.min(instructions.len())];
+        println!("lit: {}", sweep::total_in::<Lit>(instructions, area));
+        println!(
+            "brightness: {}",
+            sweep::total_in::<Brightness>(instructions, area)
+        );
+        return;
+    }
 
-    println!("{:?}", part2(input0));
-    println!("{:?}", part2(input1));
+    aoc::run::Day::new("2015/day6")
+        .input("input0", input0)
+        .input("input1", input1)
+        .parse("parse", parse_input)
+        .part(1, part1)
+        .part(2, part2)
+        .run();
 }
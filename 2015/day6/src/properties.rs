@@ -0,0 +1,62 @@
+use aoc::{generate::Rng, prop};
+
+use crate::{
+    Instruction, Op, SIZE, brightness_image, format_input, light::Lit, lights, lights_image,
+    parse_input, sweep,
+};
+
+fn instruction(rng: &mut Rng) -> Instruction {
+    let op = *rng.choose(&[Op::On, Op::Off, Op::Toggle]);
+    let (bx, by) = (rng.below(SIZE as u64), rng.below(SIZE as u64));
+    let (ex, ey) = (rng.range(bx..SIZE as u64), rng.range(by..SIZE as u64));
+
+    (op, (bx as usize, by as usize), (ex as usize, ey as usize))
+}
+
+#[test]
+fn instructions_round_trip() {
+    let instructions = |rng: &mut Rng| {
+        let len = rng.range(1..20);
+        (0..len).map(|_| instruction(rng)).collect::<Vec<_>>()
+    };
+
+    prop::check(instructions, |instructions| {
+        parse_input(&format_input(instructions)).as_ref() == Some(instructions)
+    });
+}
+
+#[test]
+fn corrupted_instructions_are_rejected() {
+    let corrupted = |rng: &mut Rng| {
+        let line = format_input(&[instruction(rng)]);
+        prop::corrupt(rng, line.trim_end())
+    };
+
+    // a deleted or doubled digit is still an instruction
+    prop::check(corrupted, |line| {
+        parse_input(line).is_none_or(|instructions| format_input(&instructions).trim_end() == line)
+    });
+}
+
+#[test]
+fn the_bit_grid_agrees_with_the_sweep() {
+    let instructions = |rng: &mut Rng| {
+        let len = rng.range(1..20);
+        (0..len).map(|_| instruction(rng)).collect::<Vec<_>>()
+    };
+
+    prop::check(instructions, |instructions| {
+        let lit = lights(&format_input(instructions)).unwrap().count_ones();
+        lit as u64 == sweep::total::<Lit>(instructions)
+    });
+}
+
+#[test]
+fn images_only_fit_the_grid() {
+    for input in ["toggle 0,0 through 999,1000", "toggle 0,0 through 1000,999"] {
+        assert!(lights_image(input).is_none(), "{input}");
+        assert!(brightness_image(input).is_none(), "{input}");
+    }
+
+    assert!(brightness_image("toggle 0,0 through 999,999").is_some());
+}
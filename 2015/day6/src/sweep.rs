@@ -0,0 +1,237 @@
+//! the lights without a grid: a sweep over the columns where some rectangle starts or ends, and
+//! in each slab of columns between them, over the rows where one of the rectangles covering it
+//! starts or ends. the instructions don't commute, so what the ones covering a light do is kept
+//! in a segment tree indexed by their order, and only the leaves of the rectangles that start or
+//! end change. no cell is ever visited, so the size of the grid doesn't matter, only how many
+//! rectangles cover each slab: every slab goes through the rows of all of them, so millions of
+//! rectangles that mostly overlap take quadratic time
+
+use std::collections::BTreeSet;
+
+use crate::{
+    Instruction, Range,
+    light::{Effect, Total},
+};
+
+/// leaves in the order of the instructions, each node the composition of its children
+struct Tree<E> {
+    nodes: Vec<E>,
+    leaves: usize,
+}
+
+impl<E: Effect> Tree<E> {
+    fn new(len: usize) -> Self {
+        let leaves = len.next_power_of_two();
+
+        Tree {
+            nodes: vec![E::NONE; 2 * leaves],
+            leaves,
+        }
+    }
+
+    fn set(&mut self, i: usize, effect: E) {
+        let mut node = self.leaves + i;
+        self.nodes[node] = effect;
+
+        while node > 1 {
+            node /= 2;
+            self.nodes[node] = self.nodes[2 * node].then(self.nodes[2 * node + 1]);
+        }
+    }
+
+    fn root(&self) -> E {
+        self.nodes[1]
+    }
+}
+
+/// `(at, instruction, starts)`, sorted so everything at `at` is handled before what's after it
+type Edge = (usize, usize, bool);
+
+/// the sum over every light of its [`Effect::value`]. coordinates are below `usize::MAX`, like
+/// the ones [`parse_input`](crate::parse_input) reads, since a rectangle ends one past its last
+/// light
+pub fn total<E: Effect>(instructions: &[Instruction]) -> E::Value {
+    let edges = |(b, e): (usize, usize), i| [(b, i, true), (e + 1, i, false)];
+
+    let mut columns = instructions
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, (bx, by), (ex, ey)))| bx <= ex && by <= ey)
+        .flat_map(|(i, &(_, (bx, _), (ex, _)))| edges((bx, ex), i))
+        .collect::<Vec<Edge>>();
+    columns.sort_unstable();
+
+    let mut tree = Tree::<E>::new(instructions.len());
+    // the rows where the rectangles covering the slab start or end, kept from one to the next
+    let mut rows = BTreeSet::<Edge>::new();
+    let mut total = E::Value::ZERO;
+
+    for (j, &(x, i, starts)) in columns.iter().enumerate() {
+        let (_, (_, by), (_, ey)) = instructions[i];
+        for edge in edges((by, ey), i) {
+            match starts {
+                true => rows.insert(edge),
+                false => rows.remove(&edge),
+            };
+        }
+
+        // the slab only ends where the next column is another one
+        let width = match columns.get(j + 1) {
+            Some(&(next, _, _)) if next != x => (next - x) as u64,
+            _ => continue,
+        };
+
+        // every rectangle that starts also ends, so the tree is empty again afterwards
+        let mut rows = rows.iter().peekable();
+        while let Some(&(y, i, starts)) = rows.next() {
+            let effect = match starts {
+                true => E::of(instructions[i].0),
+                false => E::NONE,
+            };
+            tree.set(i, effect);
+
+            if let Some(&&(next, _, _)) = rows.peek() {
+                total.add(tree.root().value(), (next - y) as u64 * width);
+            }
+        }
+    }
+
+    total
+}
+
+/// [`total`] over the lights from `from` to `to`, both included. for the total after some of
+/// the instructions, pass only those
+pub fn total_in<E: Effect>(instructions: &[Instruction], (from, to): (Range, Range)) -> E::Value {
+    // the rectangles outside come out empty, but they stay so the order is the same
+    let clipped = instructions
+        .iter()
+        .map(|&(op, (bx, by), (ex, ey))| {
+            let beg = (bx.max(from.0), by.max(from.1));
+            let end = (ex.min(to.0), ey.min(to.1));
+            (op, beg, end)
+        })
+        .collect::<Vec<_>>();
+
+    total::<E>(&clipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Op,
+        light::{
+            Brightness, Lit,
+            tests::{Colour, Counter},
+        },
+    };
+    use aoc::{generate::Rng, prop};
+
+    const SIZE: usize = 12;
+
+    fn naive<E: Effect>(instructions: &[Instruction]) -> E::Value {
+        let mut lights = [[E::NONE; SIZE]; SIZE];
+
+        for &(op, (bx, by), (ex, ey)) in instructions {
+            for row in &mut lights[bx..=ex] {
+                for light in &mut row[by..=ey] {
+                    *light = light.then(E::of(op));
+                }
+            }
+        }
+
+        let mut total = E::Value::ZERO;
+        for light in lights.iter().flatten() {
+            total.add(light.value(), 1);
+        }
+        total
+    }
+
+    fn instructions(rng: &mut Rng) -> Vec<Instruction> {
+        (0..rng.range(1..30))
+            .map(|_| {
+                let op = *rng.choose(&[Op::On, Op::Off, Op::Toggle]);
+                let (bx, by) = (rng.below(SIZE as u64), rng.below(SIZE as u64));
+                let (ex, ey) = (rng.range(bx..SIZE as u64), rng.range(by..SIZE as u64));
+
+                (op, (bx as usize, by as usize), (ex as usize, ey as usize))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn the_sweep_agrees_with_a_grid() {
+        prop::check(instructions, |i| total::<Lit>(i) == naive::<Lit>(i));
+        prop::check(instructions, |i| {
+            total::<Brightness>(i) == naive::<Brightness>(i)
+        });
+    }
+
+    #[test]
+    fn any_effect_can_be_swept() {
+        prop::check(instructions, |i| total::<Counter>(i) == naive::<Counter>(i));
+        prop::check(instructions, |i| total::<Colour>(i) == naive::<Colour>(i));
+    }
+
+    #[test]
+    fn areas_after_some_instructions() {
+        prop::check(instructions, |instructions| {
+            let len = instructions.len();
+            let area = ((2, 3), (7, SIZE - 1));
+
+            (0..=len).all(|n| {
+                // what's outside is turned off at the end, so only the area counts
+                let mut outside = instructions[..n].to_vec();
+                outside.extend([
+                    (Op::Off, (0, 0), (1, SIZE - 1)),
+                    (Op::Off, (8, 0), (SIZE - 1, SIZE - 1)),
+                    (Op::Off, (0, 0), (SIZE - 1, 2)),
+                ]);
+
+                total_in::<Lit>(&instructions[..n], area) == naive::<Lit>(&outside)
+            })
+        });
+    }
+
+    #[test]
+    fn coordinates_can_be_huge() {
+        let big = 1_000_000_000;
+        let instructions = [
+            (Op::On, (0, 0), (big - 1, big - 1)),
+            (Op::Toggle, (1, 1), (big - 2, big - 2)),
+            (Op::Off, (0, 0), (0, big - 1)),
+        ];
+
+        let (big, inner) = (big as u64, big as u64 - 2);
+        assert_eq!(total::<Lit>(&instructions), big * big - inner * inner - big);
+        assert_eq!(
+            total::<Brightness>(&instructions),
+            big * big + 2 * inner * inner - big
+        );
+    }
+
+    #[test]
+    fn coordinates_up_to_the_last_u32() {
+        let last = u32::MAX as usize;
+        let instructions = crate::parse_input(&format!(
+            "turn on {},0 through {last},1\ntoggle {last},1 through {last},{last}",
+            last - 2
+        ))
+        .unwrap();
+
+        assert_eq!(total::<Lit>(&instructions), 6 - 1 + (last as u64 - 1));
+        assert!(crate::parse_input(&format!("toggle 0,0 through 0,{}", last + 1)).is_none());
+    }
+
+    #[test]
+    fn many_instructions() {
+        // a staircase of squares, each overlapping the next one on a corner
+        let instructions = (0..200_000)
+            .map(|i| (Op::Toggle, (3 * i, 3 * i), (3 * i + 3, 3 * i + 3)))
+            .collect::<Vec<_>>();
+
+        let (len, squares) = (instructions.len() as u64, 16);
+        assert_eq!(total::<Lit>(&instructions), len * squares - 2 * (len - 1));
+        assert_eq!(total::<Brightness>(&instructions), 2 * len * squares);
+    }
+}
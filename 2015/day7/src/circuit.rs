@@ -0,0 +1,705 @@
+//! the circuit as a netlist: every wire is driven by one gate, whose inputs are signals or other
+//! wires. [`Circuit::new`] checks all of it up front, so that evaluating can't fail, and it's done
+//! in order without recursion, however deep the circuit is. wires carry a [`Word`], `u16` like
+//! in the puzzle unless another width is asked for
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use crate::word::Word;
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Gate {
+    AND,
+    OR,
+    LSHIFT,
+    RSHIFT,
+    NOT,
+    ASSIGN,
+    XOR,
+    NAND,
+    /// bit by bit, from `in1` where `in3` has a 1 and from `in2` where it has a 0
+    MUX,
+}
+
+impl Gate {
+    pub fn exec<W: Word>(self, in1: W, in2: W, in3: W) -> W {
+        match self {
+            Gate::AND => in1 & in2,
+            Gate::OR => in1 | in2,
+            Gate::LSHIFT => in1.shift_left(in2),
+            Gate::RSHIFT => in1.shift_right(in2),
+            Gate::NOT => !in2,
+            Gate::ASSIGN => in1,
+            Gate::XOR => in1 ^ in2,
+            Gate::NAND => !(in1 & in2),
+            Gate::MUX => (in3 & in1) | (!in3 & in2),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Signal<'a, W = u16> {
+    Value(W),
+    Wire(&'a str),
+}
+
+impl<'a, W: Word> Signal<'a, W> {
+    /// plain digits, `parse` alone would take a sign too
+    fn parse(s: &'a str) -> Option<Self> {
+        if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) {
+            s.parse().ok().map(Signal::Value)
+        } else if !s.is_empty() && s.bytes().all(|b| b.is_ascii_lowercase()) {
+            Some(Signal::Wire(s))
+        } else {
+            None
+        }
+    }
+
+    fn wire(self) -> Option<&'a str> {
+        match self {
+            Signal::Wire(wire) => Some(wire),
+            Signal::Value(_) => None,
+        }
+    }
+}
+
+/// the inputs a gate doesn't use are 0, and only `MUX` uses `in3`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Scheme<'a, W = u16> {
+    pub gate: Gate,
+    pub in1: Signal<'a, W>,
+    pub in2: Signal<'a, W>,
+    pub in3: Signal<'a, W>,
+}
+
+impl<'a, W: Word> Scheme<'a, W> {
+    fn wires(&self) -> impl Iterator<Item = &'a str> {
+        [self.in1, self.in2, self.in3]
+            .into_iter()
+            .filter_map(Signal::wire)
+    }
+}
+
+impl<W: Word> fmt::Display for Signal<'_, W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Signal::Value(value) => write!(f, "{value}"),
+            Signal::Wire(wire) => f.write_str(wire),
+        }
+    }
+}
+
+/// the left side of a line of the netlist, what [`parse_line`] reads
+impl<W: Word> fmt::Display for Scheme<'_, W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Scheme {
+            gate,
+            in1,
+            in2,
+            in3,
+        } = self;
+
+        match gate {
+            Gate::ASSIGN => write!(f, "{in1}"),
+            Gate::NOT => write!(f, "NOT {in2}"),
+            Gate::MUX => write!(f, "MUX {in3} {in1} {in2}"),
+            _ => write!(f, "{in1} {gate:?} {in2}"),
+        }
+    }
+}
+
+/// a line like `x AND y -> z`, `NOT x -> y`, `123 -> x` or `MUX s x y -> z`
+fn parse_line<W: Word>(line: &str) -> Option<(&str, Scheme<'_, W>)> {
+    let (lhs, wire) = line.split_once(" -> ")?;
+    let wire = Signal::<W>::parse(wire.trim())?.wire()?;
+    let zero = Signal::Value(W::default());
+
+    let scheme = match lhs.split_whitespace().collect::<Vec<_>>()[..] {
+        [in1] => Scheme {
+            gate: Gate::ASSIGN,
+            in1: Signal::parse(in1)?,
+            in2: zero,
+            in3: zero,
+        },
+        ["NOT", in2] => Scheme {
+            gate: Gate::NOT,
+            in1: zero,
+            in2: Signal::parse(in2)?,
+            in3: zero,
+        },
+        ["MUX", in3, in1, in2] => Scheme {
+            gate: Gate::MUX,
+            in1: Signal::parse(in1)?,
+            in2: Signal::parse(in2)?,
+            in3: Signal::parse(in3)?,
+        },
+        [in1, gate, in2] => Scheme {
+            gate: match gate {
+                "AND" => Gate::AND,
+                "OR" => Gate::OR,
+                "LSHIFT" => Gate::LSHIFT,
+                "RSHIFT" => Gate::RSHIFT,
+                "XOR" => Gate::XOR,
+                "NAND" => Gate::NAND,
+                _ => return None,
+            },
+            in1: Signal::parse(in1)?,
+            in2: Signal::parse(in2)?,
+            in3: zero,
+        },
+        _ => return None,
+    };
+
+    Some((wire, scheme))
+}
+
+/// what's wrong with a netlist. lines are numbered from 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    Syntax {
+        line: usize,
+        text: String,
+    },
+    /// a wire that nothing drives, and the wires that read it
+    Undefined {
+        wire: String,
+        read_by: Vec<String>,
+    },
+    /// a wire driven on more than one line
+    DuplicateDriver {
+        wire: String,
+        lines: Vec<usize>,
+    },
+    /// wires that depend on themselves: each one feeds the next, and the last feeds the first
+    Loop {
+        wires: Vec<String>,
+    },
+    /// a wire to override that isn't in the circuit
+    Unknown {
+        wire: String,
+    },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::Syntax { line, text } => {
+                write!(f, "line {line}: expected `<gate> -> <wire>`, got `{text}`")
+            }
+            Problem::Undefined { wire, read_by } => {
+                write!(
+                    f,
+                    "wire {wire} is read by {} but nothing drives it",
+                    read_by.join(", ")
+                )
+            }
+            Problem::DuplicateDriver { wire, lines } => {
+                let lines = lines.iter().map(usize::to_string).collect::<Vec<_>>();
+                write!(f, "wire {wire} is driven on lines {}", lines.join(", "))
+            }
+            Problem::Loop { wires } => {
+                write!(
+                    f,
+                    "combinational loop: {} -> {}",
+                    wires.join(" -> "),
+                    wires[0]
+                )
+            }
+            Problem::Unknown { wire } => write!(f, "there's no wire {wire} to override"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Circuit<'a, W = u16> {
+    /// in the order of the netlist, with the gate driving them
+    wires: Vec<(&'a str, Scheme<'a, W>)>,
+    index: HashMap<&'a str, usize>,
+    /// the wires that read each wire
+    readers: Vec<Vec<usize>>,
+    /// every wire after the ones it reads
+    order: Vec<usize>,
+    /// where each wire is in `order`
+    position: Vec<usize>,
+    values: Vec<W>,
+}
+
+impl<'a, W: Word> Circuit<'a, W> {
+    /// every problem of the netlist if it has any, in the order they're found in
+    pub fn new(input: &'a str) -> Result<Self, Vec<Problem>> {
+        let mut problems = Vec::new();
+        let mut wires = Vec::new();
+        let mut index = HashMap::new();
+        let mut drivers = HashMap::<&str, Vec<usize>>::new();
+
+        for (i, line) in input.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let Some((wire, scheme)) = parse_line(line) else {
+                problems.push(Problem::Syntax {
+                    line: i + 1,
+                    text: line.to_owned(),
+                });
+                continue;
+            };
+
+            drivers.entry(wire).or_default().push(i + 1);
+            index.entry(wire).or_insert_with(|| {
+                wires.push((wire, scheme));
+                wires.len() - 1
+            });
+        }
+
+        let mut undefined = Vec::<(&str, Vec<&str>)>::new();
+        for &(wire, scheme) in &wires {
+            if let Some(lines) = drivers.get(wire).filter(|lines| lines.len() > 1) {
+                problems.push(Problem::DuplicateDriver {
+                    wire: wire.to_owned(),
+                    lines: lines.clone(),
+                });
+            }
+
+            for input in scheme.wires().filter(|input| !index.contains_key(input)) {
+                match undefined
+                    .iter_mut()
+                    .find(|(undefined, _)| *undefined == input)
+                {
+                    Some((_, read_by)) => read_by.push(wire),
+                    None => undefined.push((input, vec![wire])),
+                }
+            }
+        }
+
+        problems.extend(
+            undefined
+                .into_iter()
+                .map(|(wire, read_by)| Problem::Undefined {
+                    wire: wire.to_owned(),
+                    read_by: read_by.into_iter().map(str::to_owned).collect(),
+                }),
+        );
+
+        let len = wires.len();
+        let mut circuit = Circuit {
+            wires,
+            index,
+            readers: Vec::new(),
+            order: Vec::new(),
+            position: Vec::new(),
+            values: vec![W::default(); len],
+        };
+        problems.extend(circuit.sort());
+
+        if !problems.is_empty() {
+            return Err(problems);
+        }
+
+        for i in circuit.order.clone() {
+            circuit.values[i] = circuit.compute(i);
+        }
+
+        Ok(circuit)
+    }
+
+    /// the defined wires read by the gate of wire `i`, as indices
+    fn inputs(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+        self.wires[i]
+            .1
+            .wires()
+            .filter_map(|input| self.index.get(input).copied())
+    }
+
+    /// fills `readers`, and `order` with the wires that don't depend on a loop. returns the loops
+    fn sort(&mut self) -> Vec<Problem> {
+        let len = self.wires.len();
+        let mut readers = vec![Vec::new(); len];
+        let mut pending = vec![0; len];
+
+        for (i, pending) in pending.iter_mut().enumerate() {
+            for input in self.inputs(i) {
+                readers[input].push(i);
+                *pending += 1;
+            }
+        }
+
+        self.order.clear();
+        self.position = vec![usize::MAX; len];
+
+        let mut ready = (0..len).filter(|&i| pending[i] == 0).collect::<Vec<_>>();
+        while let Some(i) = ready.pop() {
+            self.position[i] = self.order.len();
+            self.order.push(i);
+
+            for &reader in &readers[i] {
+                pending[reader] -= 1;
+                if pending[reader] == 0 {
+                    ready.push(reader);
+                }
+            }
+        }
+
+        self.readers = readers;
+        self.loops(&pending)
+    }
+
+    /// a wire left with `pending` inputs reads at least one other wire that's left, so going from
+    /// input to input among them always ends up going around a loop
+    fn loops(&self, pending: &[usize]) -> Vec<Problem> {
+        let mut loops = Vec::new();
+        let mut seen = HashSet::new();
+
+        for start in (0..pending.len()).filter(|&i| pending[i] > 0) {
+            let mut path = Vec::new();
+            let mut wire = start;
+
+            while seen.insert(wire) {
+                path.push(wire);
+                wire = self.inputs(wire).find(|&input| pending[input] > 0).unwrap();
+            }
+
+            // otherwise the path ran into the loop of an earlier one
+            if let Some(at) = path.iter().position(|&w| w == wire) {
+                // in the direction of the signal, from the wire that comes first in the netlist
+                let mut cycle = path.split_off(at);
+                cycle.reverse();
+                let first = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap();
+                cycle.rotate_left(first);
+
+                loops.push(Problem::Loop {
+                    wires: cycle.iter().map(|&w| self.wires[w].0.to_owned()).collect(),
+                });
+            }
+        }
+
+        loops
+    }
+
+    /// the value of wire `i`, from the values of its inputs
+    fn compute(&self, i: usize) -> W {
+        let scheme = self.wires[i].1;
+        let value = |signal| match signal {
+            Signal::Value(value) => value,
+            Signal::Wire(wire) => self.values[self.index[wire]],
+        };
+
+        scheme
+            .gate
+            .exec(value(scheme.in1), value(scheme.in2), value(scheme.in3))
+    }
+
+    /// drives each wire with its signal instead of its gate, and evaluates the wires that depend
+    /// on them again. `Ok` with how many wires that was. if a wire would read one that isn't
+    /// there, or make a loop, the circuit is left as it was
+    pub fn override_wires(
+        &mut self,
+        overrides: &[(&str, Signal<'a, W>)],
+    ) -> Result<usize, Vec<Problem>> {
+        let mut problems = Vec::new();
+
+        for &(wire, signal) in overrides {
+            if !self.index.contains_key(wire) {
+                problems.push(Problem::Unknown {
+                    wire: wire.to_owned(),
+                });
+            }
+
+            if let Some(input) = signal
+                .wire()
+                .filter(|input| !self.index.contains_key(input))
+            {
+                problems.push(Problem::Undefined {
+                    wire: input.to_owned(),
+                    read_by: vec![wire.to_owned()],
+                });
+            }
+        }
+
+        if !problems.is_empty() {
+            return Err(problems);
+        }
+
+        // with signals only, wires just stop reading others, so the order still holds
+        if overrides.iter().any(|(_, signal)| signal.wire().is_some()) {
+            let mut rewired = self.clone();
+            rewired.replace_gates(overrides);
+
+            let loops = rewired.sort();
+            if !loops.is_empty() {
+                return Err(loops);
+            }
+            *self = rewired;
+        } else {
+            self.replace_gates(overrides);
+        }
+
+        let changed = overrides.iter().map(|(wire, _)| self.index[wire]);
+        Ok(self.update(changed.collect()))
+    }
+
+    /// a copy of the circuit with the overrides, see [`Circuit::override_wires`]
+    pub fn overridden(&self, overrides: &[(&str, Signal<'a, W>)]) -> Result<Self, Vec<Problem>> {
+        let mut circuit = self.clone();
+        circuit.override_wires(overrides)?;
+
+        Ok(circuit)
+    }
+
+    fn replace_gates(&mut self, overrides: &[(&str, Signal<'a, W>)]) {
+        for &(wire, signal) in overrides {
+            let i = self.index[wire];
+            for input in self.inputs(i).collect::<Vec<_>>() {
+                self.readers[input].retain(|&reader| reader != i);
+            }
+
+            self.wires[i].1 = Scheme {
+                gate: Gate::ASSIGN,
+                in1: signal,
+                in2: Signal::Value(W::default()),
+                in3: Signal::Value(W::default()),
+            };
+            for input in self.inputs(i).collect::<Vec<_>>() {
+                self.readers[input].push(i);
+            }
+        }
+    }
+
+    /// evaluates the `changed` wires and everything downstream of them again, in order
+    fn update(&mut self, mut changed: Vec<usize>) -> usize {
+        let mut dirty = vec![false; self.wires.len()];
+        let mut todo = Vec::new();
+
+        while let Some(i) = changed.pop() {
+            if !dirty[i] {
+                dirty[i] = true;
+                todo.push(i);
+                changed.extend(&self.readers[i]);
+            }
+        }
+
+        todo.sort_unstable_by_key(|&i| self.position[i]);
+        for &i in &todo {
+            self.values[i] = self.compute(i);
+        }
+
+        todo.len()
+    }
+
+    /// `None` if there's no such wire
+    pub fn value(&self, wire: &str) -> Option<W> {
+        Some(self.values[*self.index.get(wire)?])
+    }
+
+    /// the gate driving `wire`, `None` if there's no such wire
+    pub fn scheme(&self, wire: &str) -> Option<Scheme<'a, W>> {
+        Some(self.wires[*self.index.get(wire)?].1)
+    }
+
+    /// the wires that `wire` depends on, itself included, in the order of the netlist.
+    /// `None` if there's no such wire
+    pub fn cone(&self, wire: &str) -> Option<Vec<&'a str>> {
+        let mut inside = vec![false; self.wires.len()];
+        let mut todo = vec![*self.index.get(wire)?];
+
+        while let Some(i) = todo.pop() {
+            if !inside[i] {
+                inside[i] = true;
+                todo.extend(self.inputs(i));
+            }
+        }
+
+        let wires = self.wires.iter().zip(inside);
+        Some(
+            wires
+                .filter(|(_, inside)| *inside)
+                .map(|((wire, _), _)| *wire)
+                .collect(),
+        )
+    }
+
+    /// every wire with its gate, in the order they're evaluated in
+    pub fn gates(&self) -> impl Iterator<Item = (&'a str, Scheme<'a, W>)> + '_ {
+        self.order.iter().map(|&i| self.wires[i])
+    }
+
+    /// every wire with its value, in the order of the netlist
+    pub fn values(&self) -> impl Iterator<Item = (&'a str, W)> + '_ {
+        self.wires
+            .iter()
+            .zip(&self.values)
+            .map(|(&(wire, _), &value)| (wire, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problems(input: &str) -> Vec<Problem> {
+        Circuit::<u16>::new(input).unwrap_err()
+    }
+
+    #[test]
+    fn loops_are_reported() {
+        let problems = problems("1 -> d\nc -> a\na AND d -> b\nNOT b -> c\nc -> e\n");
+
+        assert_eq!(
+            problems,
+            [Problem::Loop {
+                wires: ["a", "b", "c"].map(String::from).to_vec()
+            }]
+        );
+        assert_eq!(
+            problems[0].to_string(),
+            "combinational loop: a -> b -> c -> a"
+        );
+    }
+
+    #[test]
+    fn undefined_wires_and_duplicate_drivers_are_reported() {
+        let problems = problems("x AND y -> z\n1 -> x\ny OR q -> w\n2 -> x\n1 XNOR 2 -> v\n");
+
+        assert_eq!(
+            problems,
+            [
+                Problem::Syntax {
+                    line: 5,
+                    text: "1 XNOR 2 -> v".to_owned()
+                },
+                Problem::DuplicateDriver {
+                    wire: "x".to_owned(),
+                    lines: vec![2, 4]
+                },
+                Problem::Undefined {
+                    wire: "y".to_owned(),
+                    read_by: vec!["z".to_owned(), "w".to_owned()]
+                },
+                Problem::Undefined {
+                    wire: "q".to_owned(),
+                    read_by: vec!["w".to_owned()]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn signals_are_plain_digits() {
+        assert_eq!(Signal::<u16>::parse("05"), Some(Signal::Value(5)));
+        assert_eq!(Signal::<u16>::parse("+5"), None);
+        assert_eq!(Signal::<u16>::parse("-5"), None);
+        assert_eq!(Signal::<u16>::parse("65536"), None);
+        assert!(matches!(
+            problems("+5 -> a\n")[..],
+            [Problem::Syntax { line: 1, .. }]
+        ));
+    }
+
+    #[test]
+    fn overrides_only_evaluate_downstream() {
+        let input = "1 -> x\nx -> y\n2 -> z\nz AND y -> w\n5 -> q\n";
+        let mut circuit = Circuit::<u16>::new(input).unwrap();
+
+        assert_eq!(circuit.override_wires(&[("x", Signal::Value(3))]), Ok(3));
+        assert_eq!(
+            circuit.values().collect::<Vec<_>>(),
+            [("x", 3), ("y", 3), ("z", 2), ("w", 2), ("q", 5)]
+        );
+    }
+
+    #[test]
+    fn wires_can_be_rewired() {
+        let circuit = Circuit::<u16>::new("1 -> x\nNOT x -> y\n7 -> z\n").unwrap();
+        let rewired = circuit.overridden(&[("x", Signal::Wire("z"))]).unwrap();
+
+        assert_eq!(rewired.value("y"), Some(!7));
+        assert_eq!(circuit.value("y"), Some(!1));
+    }
+
+    #[test]
+    fn bad_overrides_change_nothing() {
+        let mut circuit = Circuit::<u16>::new("1 -> x\nNOT x -> y\n").unwrap();
+
+        assert_eq!(
+            circuit.override_wires(&[("x", Signal::Wire("y"))]),
+            Err(vec![Problem::Loop {
+                wires: vec!["x".to_owned(), "y".to_owned()]
+            }])
+        );
+        assert_eq!(
+            circuit.override_wires(&[("v", Signal::Wire("u"))]),
+            Err(vec![
+                Problem::Unknown {
+                    wire: "v".to_owned()
+                },
+                Problem::Undefined {
+                    wire: "u".to_owned(),
+                    read_by: vec!["v".to_owned()]
+                }
+            ])
+        );
+        assert_eq!(circuit.value("y"), Some(!1));
+    }
+
+    #[test]
+    fn shifts_past_the_width_give_0() {
+        let input = "1 LSHIFT 15 -> a\n1 LSHIFT 16 -> b\n65535 RSHIFT 40 -> c\n";
+        let circuit = Circuit::<u16>::new(input).unwrap();
+
+        assert_eq!(
+            circuit.values().collect::<Vec<_>>(),
+            [("a", 1 << 15), ("b", 0), ("c", 0)]
+        );
+    }
+
+    #[test]
+    fn words_can_have_other_widths() {
+        let input = "200 -> x\nx LSHIFT 1 -> y\nNOT x -> z\n";
+
+        assert_eq!(Circuit::<u8>::new(input).unwrap().value("y"), Some(144));
+        assert_eq!(Circuit::<u8>::new(input).unwrap().value("z"), Some(55));
+        assert_eq!(Circuit::<u64>::new(input).unwrap().value("y"), Some(400));
+        assert_eq!(Circuit::<u64>::new(input).unwrap().value("z"), Some(!200));
+
+        // a signal that doesn't fit is a mistake, not something to truncate
+        assert!(matches!(
+            Circuit::<u8>::new("256 -> x\n").unwrap_err()[..],
+            [Problem::Syntax { line: 1, .. }]
+        ));
+    }
+
+    #[test]
+    fn extra_gates() {
+        let input = "12 -> x\n10 -> y\nx XOR y -> a\nx NAND y -> b\nMUX 240 x y -> c\n";
+        let circuit = Circuit::<u8>::new(input).unwrap();
+
+        assert_eq!(circuit.value("a"), Some(0b0110));
+        assert_eq!(circuit.value("b"), Some(!0b1000));
+        assert_eq!(circuit.value("c"), Some(0b1010));
+    }
+
+    /// deep enough to overflow the stack when evaluated recursively
+    #[test]
+    fn long_chains_are_evaluated() {
+        let names = (0..100_000)
+            .map(|i: u32| {
+                // letters instead of digits
+                let digits = i.to_string();
+                let letters = digits.bytes().map(|b| (b - b'0' + b'a') as char);
+                format!("w{}", letters.collect::<String>())
+            })
+            .collect::<Vec<_>>();
+
+        let mut input = format!("1 -> {}\n", names[0]);
+        for pair in names.windows(2) {
+            input += &format!("NOT {} -> {}\n", pair[0], pair[1]);
+        }
+
+        let circuit = Circuit::<u16>::new(&input).unwrap();
+        assert_eq!(circuit.value(&names[99_999]), Some(!1));
+    }
+}
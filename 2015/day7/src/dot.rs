@@ -0,0 +1,131 @@
+//! the circuit as a Graphviz digraph: a node per wire, shaped like the gate driving it, and
+//! edges labelled with the value going through them. to draw it:
+//! ```text
+//! cargo run -p day7 -- --dot circuit.dot --cone a
+//! dot -Tsvg circuit.dot -o circuit.svg
+//! ```
+
+use std::{collections::HashSet, fmt::Write};
+
+use crate::{
+    circuit::{Circuit, Gate, Signal},
+    word::Word,
+};
+
+fn shape(gate: Gate) -> &'static str {
+    match gate {
+        Gate::AND => "invhouse",
+        Gate::OR => "invtriangle",
+        Gate::NOT => "triangle",
+        Gate::LSHIFT => "larrow",
+        Gate::RSHIFT => "rarrow",
+        Gate::ASSIGN => "ellipse",
+        Gate::XOR => "diamond",
+        Gate::NAND => "house",
+        Gate::MUX => "trapezium",
+    }
+}
+
+/// every wire, or with `cone` only the ones that wire depends on. `None` if `cone` isn't a wire
+pub fn to_dot<W: Word>(circuit: &Circuit<W>, cone: Option<&str>) -> Option<String> {
+    let wires = match cone {
+        Some(wire) => circuit.cone(wire)?,
+        None => circuit.values().map(|(wire, _)| wire).collect(),
+    };
+    let mut read = HashSet::new();
+    let mut dot =
+        String::from("digraph circuit {\n    rankdir=LR;\n    node [fontname=monospace];\n");
+
+    for &wire in &wires {
+        let scheme = circuit.scheme(wire)?;
+        let label = match scheme.gate {
+            Gate::ASSIGN => wire.to_owned(),
+            gate => format!("{wire}\\n{gate:?}"),
+        };
+        writeln!(
+            dot,
+            "    \"{wire}\" [label=\"{label}\", shape={}];",
+            shape(scheme.gate)
+        )
+        .unwrap();
+
+        let inputs = match scheme.gate {
+            Gate::ASSIGN => vec![("in1", scheme.in1)],
+            Gate::NOT => vec![("in2", scheme.in2)],
+            Gate::MUX => vec![
+                ("in1", scheme.in1),
+                ("in2", scheme.in2),
+                ("in3", scheme.in3),
+            ],
+            _ => vec![("in1", scheme.in1), ("in2", scheme.in2)],
+        };
+
+        for (slot, signal) in inputs {
+            match signal {
+                Signal::Wire(input) => {
+                    read.insert(input);
+                    let value = circuit.value(input)?;
+                    writeln!(dot, "    \"{input}\" -> \"{wire}\" [label={value}];").unwrap();
+                }
+                // a node of its own for every constant, so they don't pull the drawing together
+                Signal::Value(value) => {
+                    let node = format!("{wire}.{slot}");
+                    writeln!(dot, "    \"{node}\" [label={value}, shape=plaintext];").unwrap();
+                    writeln!(dot, "    \"{node}\" -> \"{wire}\";").unwrap();
+                }
+            }
+        }
+    }
+
+    // the value of the wires nobody reads, like the target of the cone, on an edge going nowhere
+    for &wire in wires.iter().filter(|wire| !read.contains(*wire)) {
+        let value = circuit.value(wire)?;
+        writeln!(dot, "    \"{wire}.out\" [label=\"\", shape=point];").unwrap();
+        writeln!(dot, "    \"{wire}\" -> \"{wire}.out\" [label={value}];").unwrap();
+    }
+
+    dot.push_str("}\n");
+    Some(dot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cones_only_have_what_the_wire_depends_on() {
+        let circuit = Circuit::<u16>::new(include_str!("input0.txt")).unwrap();
+        let dot = to_dot(&circuit, Some("d")).unwrap();
+
+        assert_eq!(
+            dot,
+            "digraph circuit {
+    rankdir=LR;
+    node [fontname=monospace];
+    \"x\" [label=\"x\", shape=ellipse];
+    \"x.in1\" [label=123, shape=plaintext];
+    \"x.in1\" -> \"x\";
+    \"y\" [label=\"y\", shape=ellipse];
+    \"y.in1\" [label=456, shape=plaintext];
+    \"y.in1\" -> \"y\";
+    \"d\" [label=\"d\\nAND\", shape=invhouse];
+    \"x\" -> \"d\" [label=123];
+    \"y\" -> \"d\" [label=456];
+    \"d.out\" [label=\"\", shape=point];
+    \"d\" -> \"d.out\" [label=72];
+}
+"
+        );
+        assert_eq!(to_dot(&circuit, Some("nope")), None);
+    }
+
+    #[test]
+    fn the_whole_circuit_has_every_wire() {
+        let circuit = Circuit::<u16>::new(include_str!("input0.txt")).unwrap();
+        let dot = to_dot(&circuit, None).unwrap();
+
+        for (wire, _) in circuit.values() {
+            assert!(dot.contains(&format!("    \"{wire}\" [label=")), "{wire}");
+        }
+    }
+}
@@ -0,0 +1,8 @@
+aoc::examples! {
+    part1_input0_d: part1(include_str!("input0.txt"), "d") => Some(72),
+    part1_input0_e: part1(include_str!("input0.txt"), "e") => Some(507),
+    part1_input0_f: part1(include_str!("input0.txt"), "f") => Some(492),
+    part1_input0_g: part1(include_str!("input0.txt"), "g") => Some(114),
+    part1_input0_h: part1(include_str!("input0.txt"), "h") => Some(65412),
+    part1_input0_i: part1(include_str!("input0.txt"), "i") => Some(65079),
+}
@@ -0,0 +1,125 @@
+use aoc::generate::Rng;
+
+/// `c`, `d`, ..., `z`, `aa`, `ab`, ...: `a` and `b` have a meaning
+fn wire_name(i: usize) -> String {
+    let mut i = i + 2;
+    let mut name = Vec::new();
+
+    loop {
+        name.push(b'a' + (i % 26) as u8);
+        i /= 26;
+        if i == 0 {
+            break;
+        }
+        i -= 1;
+    }
+
+    name.reverse();
+    String::from_utf8(name).unwrap()
+}
+
+/// a circuit of `size` gates without loops, in a random order. `b` is a signal, for part 2 to
+/// override, and the last gate is `a`
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut wires = vec!["b".to_owned()];
+    let mut lines = vec![format!("{} -> b", rng.below(1 << 16))];
+
+    // a gate only reads wires made before it, so there can't be loops
+    let operand = |rng: &mut Rng, wires: &[String]| {
+        if rng.chance(0.1) {
+            rng.below(1 << 16).to_string()
+        } else {
+            rng.choose(wires).clone()
+        }
+    };
+
+    for i in 0..size {
+        let lhs = match rng.below(6) {
+            0 => format!("{} AND {}", operand(rng, &wires), operand(rng, &wires)),
+            1 => format!("{} OR {}", operand(rng, &wires), operand(rng, &wires)),
+            2 => format!("{} LSHIFT {}", rng.choose(&wires), rng.range(1..16)),
+            3 => format!("{} RSHIFT {}", rng.choose(&wires), rng.range(1..16)),
+            4 => format!("NOT {}", rng.choose(&wires)),
+            _ => operand(rng, &wires),
+        };
+
+        let wire = if i + 1 == size {
+            "a".to_owned()
+        } else {
+            wire_name(i)
+        };
+        lines.push(format!("{lhs} -> {wire}"));
+        wires.push(wire);
+    }
+
+    rng.shuffle(&mut lines);
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use super::*;
+    use crate::{circuit::Circuit, part1};
+    use aoc::generate;
+
+    /// the straightforward way: go over the gates until all of them have a value
+    fn simulate(input: &str) -> HashMap<&str, u16> {
+        let gates = input
+            .lines()
+            .map(|line| {
+                let (lhs, wire) = line.split_once(" -> ").unwrap();
+                (wire, lhs.split(' ').collect::<Vec<_>>())
+            })
+            .collect::<Vec<_>>();
+        let mut values = HashMap::new();
+
+        while values.len() < gates.len() {
+            for (wire, lhs) in &gates {
+                let value = |s: &str| s.parse().ok().or_else(|| values.get(s).copied());
+
+                let result = match lhs[..] {
+                    [a] => value(a),
+                    ["NOT", a] => value(a).map(|a: u16| !a),
+                    [a, "AND", b] => value(a).zip(value(b)).map(|(a, b)| a & b),
+                    [a, "OR", b] => value(a).zip(value(b)).map(|(a, b)| a | b),
+                    [a, "LSHIFT", b] => value(a).zip(value(b)).map(|(a, b)| a << b),
+                    [a, "RSHIFT", b] => value(a).zip(value(b)).map(|(a, b)| a >> b),
+                    _ => panic!("unknown gate {lhs:?}"),
+                };
+
+                if let Some(result) = result {
+                    values.insert(*wire, result);
+                }
+            }
+        }
+
+        values
+    }
+
+    #[test]
+    fn generated_circuits() {
+        for (seed, input) in generate::inputs(input, 200) {
+            // a gate per wire, `b` a signal and `a` the last one
+            let mut wires = HashSet::new();
+            for line in input.lines() {
+                let (_, wire) = line.split_once(" -> ").unwrap();
+                assert!(wires.insert(wire), "seed {seed}: {wire} driven twice");
+            }
+            assert_eq!(wires.len(), 201, "seed {seed}");
+            assert!(wires.contains("a") && wires.contains("b"), "seed {seed}");
+            let b = input.lines().find_map(|line| line.strip_suffix(" -> b"));
+            assert!(b.is_some_and(|b| b.parse::<u16>().is_ok()), "seed {seed}");
+
+            let values = simulate(&input);
+            assert_eq!(part1(&input, "a"), values.get("a").copied(), "seed {seed}");
+
+            let circuit = Circuit::<u16>::new(&input).unwrap();
+            for (wire, value) in circuit.values() {
+                assert_eq!(values.get(wire), Some(&value), "seed {seed}, wire {wire}");
+            }
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! a smaller netlist with the same outputs: gates fed by signals become signals, wires that only
+//! copy another one disappear, and so does whatever the outputs don't depend on. since every wire
+//! of the puzzle comes down to signals, it all folds into one line per output, unless some wires
+//! are kept `free`: they keep their gate but nothing is folded through them, which shows how the
+//! outputs depend on them. e.g. to see what part 2 changes:
+//! ```text
+//! cargo run -p day7 -- --simplify a --free b
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    circuit::{Circuit, Gate, Scheme, Signal},
+    word::Word,
+};
+
+/// the signal or wire a gate always gives, if there's one
+fn reduce<W: Word>(scheme: Scheme<'_, W>) -> Option<Signal<'_, W>> {
+    use Signal::Value;
+
+    let zero = W::default();
+    let ones = !zero;
+
+    // the inputs a gate doesn't use are signals too
+    if let [Value(in1), Value(in2), Value(in3)] = [scheme.in1, scheme.in2, scheme.in3] {
+        return Some(Value(scheme.gate.exec(in1, in2, in3)));
+    }
+
+    match (scheme.gate, scheme.in1, scheme.in2, scheme.in3) {
+        (Gate::ASSIGN, x, _, _) => Some(x),
+        (Gate::AND | Gate::OR, x, y, _) if x == y => Some(x),
+        (Gate::XOR, x, y, _) if x == y => Some(Value(zero)),
+        (Gate::AND, x, Value(v), _) | (Gate::AND, Value(v), x, _) if v == ones => Some(x),
+        (Gate::AND, _, Value(v), _) | (Gate::AND, Value(v), _, _) if v == zero => Some(Value(zero)),
+        (Gate::OR | Gate::XOR, x, Value(v), _) | (Gate::OR | Gate::XOR, Value(v), x, _)
+            if v == zero =>
+        {
+            Some(x)
+        }
+        (Gate::OR, _, Value(v), _) | (Gate::OR, Value(v), _, _) if v == ones => Some(Value(ones)),
+        (Gate::LSHIFT | Gate::RSHIFT, x, Value(v), _) if v == zero => Some(x),
+        (Gate::MUX, x, _, Value(v)) if v == ones => Some(x),
+        (Gate::MUX, _, y, Value(v)) if v == zero => Some(y),
+        (Gate::MUX, x, y, _) if x == y => Some(x),
+        _ => None,
+    }
+}
+
+/// the netlist of what `outputs` depend on, in evaluation order. `None` if one of the `outputs`
+/// or `free` wires isn't in the circuit. like the circuit, which [`Circuit::new`] only builds if
+/// no wire is driven twice, it has a single gate per wire, even if an output is repeated
+pub fn simplify<'a, W: Word>(
+    circuit: &Circuit<'a, W>,
+    outputs: &[&'a str],
+    free: &[&str],
+) -> Option<Vec<(&'a str, Scheme<'a, W>)>> {
+    if let Some(missing) = outputs
+        .iter()
+        .chain(free)
+        .find(|w| circuit.scheme(w).is_none())
+    {
+        aoc::warn!("there's no wire {missing}");
+        return None;
+    }
+
+    // what the wires that are gone were replaced by. they're handled in evaluation order, so
+    // these never point to a wire that's gone itself
+    let mut replaced = HashMap::new();
+    let mut gates = Vec::new();
+
+    for (wire, scheme) in circuit.gates() {
+        let resolve = |signal| match signal {
+            Signal::Wire(input) => replaced.get(input).copied().unwrap_or(signal),
+            Signal::Value(_) => signal,
+        };
+        let scheme = Scheme {
+            in1: resolve(scheme.in1),
+            in2: resolve(scheme.in2),
+            in3: resolve(scheme.in3),
+            ..scheme
+        };
+
+        match reduce(scheme).filter(|_| !free.contains(&wire)) {
+            Some(signal) => {
+                replaced.insert(wire, signal);
+            }
+            None => gates.push((wire, scheme)),
+        }
+    }
+
+    // the outputs keep their names, even if they only copy another wire
+    let mut live = HashSet::new();
+    let mut todo = outputs.to_vec();
+    while let Some(wire) = todo.pop() {
+        if live.insert(wire) {
+            let scheme = gates
+                .iter()
+                .find(|(gate, _)| *gate == wire)
+                .map(|(_, s)| *s);
+            let inputs = scheme.map(|s| [s.in1, s.in2, s.in3]);
+            let inputs = inputs.unwrap_or_else(|| [replaced[wire]; 3]);
+            todo.extend(inputs.into_iter().filter_map(|signal| match signal {
+                Signal::Wire(input) => Some(input),
+                Signal::Value(_) => None,
+            }));
+        }
+    }
+
+    let mut simplified = gates
+        .into_iter()
+        .filter(|(wire, _)| live.contains(wire))
+        .collect::<Vec<_>>();
+
+    let mut copied = HashSet::new();
+    for &wire in outputs {
+        if let Some(&signal) = replaced.get(wire).filter(|_| copied.insert(wire)) {
+            let zero = Signal::Value(W::default());
+            simplified.push((
+                wire,
+                Scheme {
+                    gate: Gate::ASSIGN,
+                    in1: signal,
+                    in2: zero,
+                    in3: zero,
+                },
+            ));
+        }
+    }
+
+    Some(simplified)
+}
+
+/// in the syntax of the puzzle, so it can be read back
+pub fn to_netlist<W: Word>(gates: &[(&str, Scheme<'_, W>)]) -> String {
+    gates
+        .iter()
+        .map(|(wire, scheme)| format!("{scheme} -> {wire}\n"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate;
+
+    #[test]
+    fn chains_and_constants_fold() {
+        let input = "1 -> b\nb -> c\nc AND x -> d\n65535 -> x\nd -> a\n5 -> dead\n";
+        let circuit = Circuit::<u16>::new(input).unwrap();
+
+        let simplified = simplify(&circuit, &["a"], &[]).unwrap();
+        assert_eq!(to_netlist(&simplified), "1 -> a\n");
+
+        let simplified = simplify(&circuit, &["a"], &["b"]).unwrap();
+        assert_eq!(to_netlist(&simplified), "1 -> b\nb -> a\n");
+    }
+
+    #[test]
+    fn repeated_outputs_are_driven_once() {
+        let circuit = Circuit::<u16>::new("1 -> b\nb -> a\n").unwrap();
+        let simplified = simplify(&circuit, &["a", "b", "a"], &[]).unwrap();
+        assert_eq!(to_netlist(&simplified), "1 -> a\n1 -> b\n");
+    }
+
+    #[test]
+    fn simplified_circuits_give_the_same_outputs() {
+        for (seed, input) in aoc::generate::inputs(generate::input, 200) {
+            let circuit = Circuit::<u16>::new(&input).unwrap();
+            let outputs = ["a", "b", "c"];
+
+            let netlist = to_netlist(&simplify(&circuit, &outputs, &["b"]).unwrap());
+            let simplified = Circuit::<u16>::new(&netlist).unwrap();
+
+            for output in outputs {
+                assert_eq!(
+                    simplified.value(output),
+                    circuit.value(output),
+                    "seed {seed}"
+                );
+            }
+            assert!(simplified.values().count() < circuit.values().count());
+
+            // a different `b` goes through the same way
+            let b = [("b", Signal::Value(12345))];
+            let (circuit, simplified) = (circuit.overridden(&b), simplified.overridden(&b));
+            assert_eq!(
+                simplified.unwrap().value("a"),
+                circuit.unwrap().value("a"),
+                "seed {seed}"
+            );
+        }
+    }
+}
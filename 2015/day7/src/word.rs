@@ -0,0 +1,49 @@
+//! the words going through the wires: 16 bits in the puzzle, but any unsigned integer works
+
+use std::{
+    fmt::{Debug, Display},
+    ops::{BitAnd, BitOr, BitXor, Not},
+    str::FromStr,
+};
+
+pub trait Word:
+    Copy
+    + Eq
+    + Default
+    + Debug
+    + Display
+    + FromStr
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Not<Output = Self>
+{
+    /// `self << amount`, 0 once every bit is shifted out instead of overflowing
+    fn shift_left(self, amount: Self) -> Self;
+    /// `self >> amount`, 0 once every bit is shifted out instead of overflowing
+    fn shift_right(self, amount: Self) -> Self;
+}
+
+macro_rules! word {
+    ($($t:ty),*) => {
+        $(
+            impl Word for $t {
+                fn shift_left(self, amount: Self) -> Self {
+                    u32::try_from(amount)
+                        .ok()
+                        .and_then(|amount| self.checked_shl(amount))
+                        .unwrap_or(0)
+                }
+
+                fn shift_right(self, amount: Self) -> Self {
+                    u32::try_from(amount)
+                        .ok()
+                        .and_then(|amount| self.checked_shr(amount))
+                        .unwrap_or(0)
+                }
+            }
+        )*
+    };
+}
+
+word!(u8, u16, u32, u64);
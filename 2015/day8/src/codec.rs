@@ -0,0 +1,214 @@
+//! string literals: quoted, with `\\`, `\"` and `\xNN` escapes like in the puzzle, and in the
+//! [`Syntax::Extended`] syntax also `\n`, `\t` and `\u{...}`. what's inside is bytes, since `\xNN`
+//! can be any of them. whatever [`encode`] gives, [`decode`] turns back into the same bytes
+
+use std::fmt::{self, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Syntax {
+    /// `\\`, `\"` and `\xNN`
+    Puzzle,
+    /// the puzzle's escapes, `\n`, `\t` and `\u{...}` with up to 6 hex digits
+    Extended,
+}
+
+/// what's wrong with a literal. `at` is the byte it's found at, the opening quote being 0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// doesn't start with a quote
+    Unquoted,
+    /// ends before its closing quote
+    Unterminated { at: usize },
+    /// goes on after its closing quote
+    Trailing { at: usize },
+    /// a backslash followed by something that isn't an escape
+    Escape { at: usize, found: char },
+    /// `\x` without two hex digits
+    Hex { at: usize },
+    /// `\u` without braces, or without the hex code of a char in them
+    Unicode { at: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Unquoted => write!(f, "a literal starts with a quote"),
+            Error::Unterminated { at } => write!(f, "byte {at}: missing closing quote"),
+            Error::Trailing { at } => write!(f, "byte {at}: something after the closing quote"),
+            Error::Escape { at, found } => write!(f, "byte {at}: unknown escape `\\{found}`"),
+            Error::Hex { at } => write!(f, "byte {at}: `\\x` takes two hex digits"),
+            Error::Unicode { at } => write!(f, "byte {at}: `\\u` takes `{{hex code of a char}}`"),
+        }
+    }
+}
+
+fn hex_digit(b: Option<&u8>) -> Option<u32> {
+    char::from(*b?).to_digit(16)
+}
+
+/// the bytes in a literal, quotes included
+pub fn decode(literal: &str, syntax: Syntax) -> Result<Vec<u8>, Error> {
+    let bytes = literal.as_bytes();
+    let extended = syntax == Syntax::Extended;
+
+    if bytes.first() != Some(&b'"') {
+        return Err(Error::Unquoted);
+    }
+
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 1;
+
+    loop {
+        let at = i;
+        let Some(&b) = bytes.get(i) else {
+            return Err(Error::Unterminated { at });
+        };
+        i += 1;
+
+        match b {
+            b'"' if i == bytes.len() => return Ok(decoded),
+            b'"' => return Err(Error::Trailing { at }),
+            b'\\' => {
+                let Some(&escape) = bytes.get(i) else {
+                    return Err(Error::Unterminated { at: i });
+                };
+                i += 1;
+
+                match escape {
+                    b'\\' | b'"' => decoded.push(escape),
+                    b'n' if extended => decoded.push(b'\n'),
+                    b't' if extended => decoded.push(b'\t'),
+                    b'x' => {
+                        let high = hex_digit(bytes.get(i)).ok_or(Error::Hex { at })?;
+                        let low = hex_digit(bytes.get(i + 1)).ok_or(Error::Hex { at })?;
+                        decoded.push((high << 4 | low) as u8);
+                        i += 2;
+                    }
+                    b'u' if extended => {
+                        let rest = &literal[i..];
+                        let (code, _) = rest
+                            .strip_prefix('{')
+                            .and_then(|rest| rest.split_once('}'))
+                            .ok_or(Error::Unicode { at })?;
+                        let c = Some(code)
+                            .filter(|code| (1..=6).contains(&code.len()))
+                            .filter(|code| code.bytes().all(|b| b.is_ascii_hexdigit()))
+                            .and_then(|code| u32::from_str_radix(code, 16).ok())
+                            .and_then(char::from_u32)
+                            .ok_or(Error::Unicode { at })?;
+                        decoded.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                        i += code.len() + 2;
+                    }
+                    _ => {
+                        // the whole char, even if it takes more than a byte
+                        let found = literal[i - 1..].chars().next().unwrap();
+                        return Err(Error::Escape { at, found });
+                    }
+                }
+            }
+            _ => decoded.push(b),
+        }
+    }
+}
+
+/// a literal for `bytes`, quotes included. only printable ASCII is left as it is, along with
+/// any other char in the extended syntax
+pub fn encode(bytes: &[u8], syntax: Syntax) -> String {
+    let extended = syntax == Syntax::Extended;
+    let mut encoded = String::with_capacity(bytes.len() + 2);
+    encoded.push('"');
+
+    for chunk in bytes.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            match c {
+                '\\' | '"' => {
+                    encoded.push('\\');
+                    encoded.push(c);
+                }
+                '\n' if extended => encoded.push_str("\\n"),
+                '\t' if extended => encoded.push_str("\\t"),
+                ' '..='~' => encoded.push(c),
+                _ if extended && !c.is_control() => encoded.push(c),
+                _ if extended && !c.is_ascii() => write!(encoded, "\\u{{{:x}}}", c as u32).unwrap(),
+                _ => {
+                    for b in c.encode_utf8(&mut [0; 4]).bytes() {
+                        write!(encoded, "\\x{b:02x}").unwrap();
+                    }
+                }
+            }
+        }
+
+        for b in chunk.invalid() {
+            write!(encoded, "\\x{b:02x}").unwrap();
+        }
+    }
+
+    encoded.push('"');
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::{generate::Rng, prop};
+
+    /// mostly the characters that need escaping, and any byte now and then
+    fn bytes(rng: &mut Rng) -> Vec<u8> {
+        let special = "\\\"\n\tx{}é€ a".as_bytes();
+
+        (0..rng.below(20))
+            .map(|_| match rng.chance(0.8) {
+                true => *rng.choose(special),
+                false => rng.below(256) as u8,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn encoded_bytes_decode_back() {
+        for syntax in [Syntax::Puzzle, Syntax::Extended] {
+            prop::check(bytes, |b| {
+                decode(&encode(b, syntax), syntax).as_ref() == Ok(b)
+            });
+        }
+    }
+
+    #[test]
+    fn the_puzzle_syntax_only_escapes_what_it_has_to() {
+        assert_eq!(encode(br#"aaa"aaa"#, Syntax::Puzzle), r#""aaa\"aaa""#);
+        assert_eq!(encode(br"\x27", Syntax::Puzzle), r#""\\x27""#);
+        assert_eq!(encode(b"\n\xff", Syntax::Puzzle), r#""\x0a\xff""#);
+        assert_eq!(encode("é\t".as_bytes(), Syntax::Extended), r#""é\t""#);
+        assert_eq!(encode("\u{85}".as_bytes(), Syntax::Extended), r#""\u{85}""#);
+    }
+
+    #[test]
+    fn escapes_decode() {
+        assert_eq!(
+            decode(r#""a\\b\"c\x41""#, Syntax::Puzzle),
+            Ok(br#"a\b"cA"#.to_vec())
+        );
+        assert_eq!(
+            decode(r#""\n\t\u{e9}""#, Syntax::Extended),
+            Ok("\n\té".as_bytes().to_vec())
+        );
+    }
+
+    #[test]
+    fn malformed_literals_say_where() {
+        let puzzle = |literal| decode(literal, Syntax::Puzzle);
+        let extended = |literal| decode(literal, Syntax::Extended);
+
+        assert_eq!(puzzle("abc"), Err(Error::Unquoted));
+        assert_eq!(puzzle(r#""abc"#), Err(Error::Unterminated { at: 4 }));
+        assert_eq!(puzzle(r#""abc\""#), Err(Error::Unterminated { at: 6 }));
+        assert_eq!(puzzle(r#""ab"c""#), Err(Error::Trailing { at: 3 }));
+        assert_eq!(puzzle(r#""a\n""#), Err(Error::Escape { at: 2, found: 'n' }));
+        assert_eq!(puzzle(r#""a\é""#), Err(Error::Escape { at: 2, found: 'é' }));
+        assert_eq!(puzzle(r#""a\x4g""#), Err(Error::Hex { at: 2 }));
+        assert_eq!(puzzle(r#""a\x4"#), Err(Error::Hex { at: 2 }));
+        assert_eq!(extended(r#""\u{110000}""#), Err(Error::Unicode { at: 1 }));
+        assert_eq!(extended(r#""\u{}""#), Err(Error::Unicode { at: 1 }));
+        assert_eq!(extended(r#""\u41""#), Err(Error::Unicode { at: 1 }));
+    }
+}
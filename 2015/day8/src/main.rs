@@ -1,69 +1,20 @@
-#[derive(Debug)]
-enum State {
-    Str,
-    Escape,
-    Hex1,
-    Hex2(u8),
-}
+mod codec;
+#[cfg(test)]
+mod examples;
 
-fn hex_val(c: u8) -> u8 {
-    match c {
-        b'0'..=b'9' => c - b'0',
-        b'a'..=b'f' => 10 + (c - b'a'),
-        b'A'..=b'F' => 10 + (c - b'A'),
-        _ => unreachable!(),
-    }
-}
+use codec::Syntax;
 
-fn parse_input1(input: &str) -> Vec<(&str, Vec<u8>)> {
+/// each line with what it decodes to, `None` if one doesn't
+fn parse_input1(input: &str) -> Option<Vec<(&str, Vec<u8>)>> {
     input
         .lines()
-        .map(|line| {
-            let s = &line[1..line.len() - 1];
-            let mut s2 = Vec::with_capacity(s.len());
-
-            let mut state = State::Str;
-
-            for b in s.bytes() {
-                state = match state {
-                    State::Str => match b {
-                        b'\\' => State::Escape,
-                        _ => {
-                            s2.push(b);
-
-                            State::Str
-                        }
-                    },
-                    State::Escape => match b {
-                        b'x' => State::Hex1,
-                        _ => {
-                            s2.push(b);
-
-                            State::Str
-                        }
-                    },
-                    State::Hex1 => match b {
-                        b'0'..=b'9' | b'a'..=b'f' | b'A'..=b'F' => {
-                            let high = hex_val(b);
-
-                            State::Hex2(high)
-                        }
-                        _ => panic!("{state:?}"),
-                    },
-
-                    State::Hex2(high) => match b {
-                        b'0'..=b'9' | b'a'..=b'f' | b'A'..=b'F' => {
-                            let low = hex_val(b);
-                            s2.push((high << 4) | low);
-
-                            State::Str
-                        }
-                        _ => panic!("{state:?}"),
-                    },
-                }
+        .enumerate()
+        .map(|(i, line)| match codec::decode(line, Syntax::Puzzle) {
+            Ok(decoded) => Some((line, decoded)),
+            Err(e) => {
+                aoc::error!("line {}: {e}", i + 1);
+                None
             }
-
-            (line, s2)
         })
         .collect()
 }
@@ -71,35 +22,19 @@ fn parse_input1(input: &str) -> Vec<(&str, Vec<u8>)> {
 fn parse_input2(input: &str) -> Vec<(&str, String)> {
     input
         .lines()
-        .map(|line| {
-            //let s2 = format!("{line:?}"); --- easy mode
-            let mut s2 = String::with_capacity(line.len()+2);
-            s2.push('"');
-
-            for c in line.chars() {
-                match c {
-                    '\\' | '"' => s2.push('\\'),
-                    _ => ()
-                }
-                s2.push(c);
-            }
-
-            s2.push('"');
-
-            (line, s2)
-        })
+        .map(|line| (line, codec::encode(line.as_bytes(), Syntax::Puzzle)))
         .collect()
 }
 
-fn part1(input: &str) -> usize {
-    let strs = parse_input1(input);
+fn part1(input: &str) -> Option<usize> {
+    let strs = parse_input1(input)?;
 
     let sums = strs
         .iter()
         .map(|(s1, s2)| (s1.len(), s2.len()))
         .fold((0, 0), |acc, x| (acc.0 + x.0, acc.1 + x.1));
 
-    sums.0 - sums.1
+    Some(sums.0 - sums.1)
 }
 
 fn part2(input: &str) -> usize {
@@ -117,9 +52,12 @@ fn main() {
     let input0 = include_str!("input0.txt");
     let input1 = include_str!("input1.txt");
 
-    println!("{:?}", part1(input0));
-    println!("{:?}", part1(input1));
-
-    println!("{:?}", part2(input0));
-    println!("{:?}", part2(input1));
+    aoc::run::Day::new("2015/day8")
+        .input("input0", input0)
+        .input("input1", input1)
+        .parse("parse1", parse_input1)
+        .parse("parse2", parse_input2)
+        .part(1, part1)
+        .part(2, part2)
+        .run();
 }
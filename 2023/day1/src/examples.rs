@@ -0,0 +1,15 @@
+const PART2_EXAMPLE: &str = "\
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen
+";
+
+aoc::examples! {
+    part1_input0: part1(include_str!("input0.txt")) => Some(142),
+    part2_input0: part2(include_str!("input0.txt")) => Some(142),
+    part2_example: part2(PART2_EXAMPLE) => Some(281),
+}
@@ -1,7 +1,14 @@
+#[cfg(test)]
+mod examples;
+
 fn main() {
     let input = include_str!("input1.txt");
-    println!("{:?}", part1(input));
-    println!("{:?}", part2(input));
+
+    aoc::run::Day::new("2023/day1")
+        .input("input1", input)
+        .part(1, part1)
+        .part(2, part2)
+        .run();
 }
 
 fn part1(input: &str) -> Option<u32> {
@@ -32,31 +39,32 @@ fn part2(input: &str) -> Option<u32> {
     let mut total = 0;
 
     for line in input.lines() {
+        // a line can have only spelled digits
         let mut first = line
             .char_indices()
             .filter_map(|(p, c)| c.to_digit(10).map(|n| (p, n)))
-            .next()?;
+            .next();
 
         let mut last = line
             .char_indices()
             .filter_map(|(p, c)| c.to_digit(10).map(|n| (p, n)))
-            .next_back()?;
+            .next_back();
 
         for (letters, digit) in spelled_digits_mapping {
             if let Some(p) = line.find(letters) {
-                if first.0 > p {
-                    first = (p, digit);
+                if first.is_none_or(|(f, _)| f > p) {
+                    first = Some((p, digit));
                 }
             }
 
             if let Some(p) = line.rfind(letters) {
-                if last.0 < p {
-                    last = (p, digit);
+                if last.is_none_or(|(l, _)| l < p) {
+                    last = Some((p, digit));
                 }
             }
         }
 
-        total += first.1 * 10 + last.1;
+        total += first?.1 * 10 + last?.1;
     }
     Some(total)
 }
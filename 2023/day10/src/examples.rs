@@ -0,0 +1,82 @@
+const SQUARE_LOOP: &str = "\
+-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF
+";
+
+const COMPLEX_LOOP: &str = "\
+7-F7-
+.FJ|7
+SJLL7
+|F--J
+LJ.LJ
+";
+
+const SQUEEZED_LOOP: &str = "\
+..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........
+";
+
+const LARGER_LOOP: &str = "\
+.F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...
+";
+
+const JUNK_LOOP: &str = "\
+FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L
+";
+
+const SQUEEZED_LOOP_INSIDE: &str = "\
+..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|II||II|.
+.L--JL--J.
+..........";
+
+fn last_frame(input: &str) -> Option<String> {
+    let mut animation = Animation::headless();
+    animate(&Input::from(input), &mut animation)?;
+
+    animation.last_frame().map(ToString::to_string)
+}
+
+aoc::examples! {
+    part1_square: part1(&Input::from(SQUARE_LOOP)) => Some(4),
+    part1_complex: part1(&Input::from(COMPLEX_LOOP)) => Some(8),
+    part1_input0: part1(&Input::from(include_str!("input0.txt"))) => Some(23),
+    part2_input0: part2(&Input::from(include_str!("input0.txt"))) => Some(4),
+    part2_squeezed: part2(&Input::from(SQUEEZED_LOOP)) => Some(4),
+    part2_larger: part2(&Input::from(LARGER_LOOP)) => Some(8),
+    part2_junk: part2(&Input::from(JUNK_LOOP)) => Some(10),
+    trace_squeezed: last_frame(SQUEEZED_LOOP) => Some(SQUEEZED_LOOP_INSIDE.to_owned()),
+}
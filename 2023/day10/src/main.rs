@@ -1,3 +1,12 @@
+#[cfg(test)]
+mod examples;
+
+use aoc::{
+    anim::{Animation, Color, Frame},
+    bitgrid::BitGrid,
+    image::Image,
+};
+
 struct Input {
     data: String,
     width: usize,
@@ -47,49 +56,34 @@ impl Input {
         }
     }
 
-    fn count_tiles_inside_path(&self, walls: &[Option<Pipe>]) -> u64 {
-        let mut count = 0;
-        let horizontal_pipe = Pipe::try_from('-').unwrap();
+    fn tiles_inside_path(&self, walls: &Walls) -> BitGrid {
+        let mut tiles = BitGrid::new(self.width, self.height);
 
         for y in 0..self.height {
             let mut inside = false;
 
             for x in 0..self.width {
-                let curr = walls[self.coords_2d_to_1d((x, y))];
-
-                match curr {
-                    Some(pipe) if pipe != horizontal_pipe => inside = !inside,
-                    None if inside => {
-                        println!("{x}, {y}, {}", self.coords_2d_to_1d((x, y)));
-                        count += 1;
-                    }
-                    _ => (),
+                // only count pipes going up, so that `F--J` is crossed once and `F--7` is not crossed
+                if walls.up.get(x, y) {
+                    inside = !inside;
+                } else if inside && !walls.all.get(x, y) {
+                    aoc::trace!("inside: {x}, {y}");
+                    tiles.set(x, y, true);
                 }
             }
         }
 
-        count
-    }
-
-    fn draw(&self) {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                print!("{}", self.get((x, y)));
-            }
-            println!();
-        }
+        tiles
     }
 
-    fn draw_walls(&self, walls: &[Option<Pipe>]) {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                match walls[self.coords_2d_to_1d((x, y))] {
-                    Some(_) => print!("X"),
-                    None => print!("."),
-                }
-            }
-            println!();
-        }
+    /// the pipes of the loop in colour, and the tiles inside it as `I`
+    fn frame(&self, walls: &Walls, inside: &BitGrid) -> Frame {
+        Frame::from_fn(self.width, self.height, |x, y| match self.get((x, y)) {
+            'S' => ('S', Color::Red),
+            c if walls.all.get(x, y) => (c, Color::Yellow),
+            _ if inside.get(x, y) => ('I', Color::Green),
+            c => (c, Color::Gray),
+        })
     }
 }
 
@@ -160,6 +154,27 @@ impl Pipe {
     }
 }
 
+/// the tiles of the loop, and those of its pipes that go up
+struct Walls {
+    all: BitGrid,
+    up: BitGrid,
+}
+
+impl Walls {
+    fn new(input: &Input) -> Self {
+        Walls {
+            all: BitGrid::new(input.width, input.height),
+            up: BitGrid::new(input.width, input.height),
+        }
+    }
+
+    fn add(&mut self, (x, y): (usize, usize), pipe: Pipe) {
+        self.all.set(x, y, true);
+        self.up
+            .set(x, y, pipe.0 == Direction::Up || pipe.1 == Direction::Up);
+    }
+}
+
 #[derive(Copy, Clone)]
 struct Tile {
     pos: (usize, usize),
@@ -211,10 +226,25 @@ fn main() {
         panic!("input not in ascii");
     }
 
-    let input = Input::from(input);
+    let parsed = Input::from(input);
+
+    if let Some(dir) = aoc::image::dir_from_args() {
+        let image = loop_image(&parsed).expect("no loop");
+        image.save(dir.join("loop.png")).unwrap();
+        return;
+    }
+
+    if let Some(mut animation) = Animation::from_args() {
+        animate(&parsed, &mut animation);
+        return;
+    }
 
-    println!("{:?}", part1(&input));
-    println!("{:?}", part2(&input));
+    aoc::run::Day::new("2023/day10")
+        .input("input0", input)
+        .parse("parse", Input::from)
+        .part(1, |input| part1(&Input::from(input)))
+        .part(2, |input| part2(&Input::from(input)))
+        .run();
 }
 
 fn part1(input: &Input) -> Option<u64> {
@@ -237,32 +267,59 @@ fn part1(input: &Input) -> Option<u64> {
     Some(steps)
 }
 
-fn part2(input: &Input) -> Option<u64> {
+/// the pipes of the loop, `on_step` sees them after each step of the two paths around it
+fn trace_loop(input: &Input, mut on_step: impl FnMut(&Walls)) -> Option<Walls> {
     let origin = input.find_origin()?;
     let mut paths = Tile::find_paths_from_origin(origin, input);
-    let mut walls = vec![None; input.width * input.height];
+    let mut walls = Walls::new(input);
 
-    walls[input.coords_2d_to_1d(origin)] = Some(Pipe::from_neighbors(paths[0].from, paths[1].from));
+    walls.add(origin, Pipe::from_neighbors(paths[0].from, paths[1].from));
     for path in paths.iter() {
-        walls[input.coords_2d_to_1d(path.pos)] = Some(path.pipe);
+        walls.add(path.pos, path.pipe);
     }
+    on_step(&walls);
 
     loop {
         for path in paths.iter_mut() {
             *path = Tile::try_from(*path, input)?;
-            walls[input.coords_2d_to_1d(path.pos)] = Some(path.pipe);
+            walls.add(path.pos, path.pipe);
         }
+        on_step(&walls);
 
         if paths[0].pos == paths[1].pos {
             break;
         }
     }
 
-    println!("w: {}, h: {}", input.width, input.height);
-    println!();
-    input.draw();
-    println!();
-    input.draw_walls(&walls);
-    println!();
-    Some(input.count_tiles_inside_path(&walls))
+    Some(walls)
+}
+
+fn animate(input: &Input, animation: &mut Animation) -> Option<()> {
+    let walls = trace_loop(input, |walls| {
+        animation.show(input.frame(walls, &BitGrid::new(input.width, input.height)));
+    })?;
+
+    let inside = input.tiles_inside_path(&walls);
+    animation.show(input.frame(&walls, &inside));
+
+    Some(())
+}
+
+fn loop_image(input: &Input) -> Option<Image> {
+    let walls = trace_loop(input, |_| ())?;
+    let inside = input.tiles_inside_path(&walls);
+
+    Some(Image::from_frame(&input.frame(&walls, &inside)).scaled(4))
+}
+
+fn part2(input: &Input) -> Option<u64> {
+    let walls = trace_loop(input, |_| ())?;
+    let inside = input.tiles_inside_path(&walls);
+
+    aoc::debug!("w: {}, h: {}", input.width, input.height);
+    aoc::trace!("{}", input.frame(&walls, &inside));
+
+    let count = inside.count_ones();
+
+    Some(count as u64)
 }
@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod examples;
+
 use std::collections::HashSet;
 
 #[derive(Debug)]
@@ -8,8 +11,12 @@ struct Scratchcard {
 
 fn main() {
     let input = include_str!("input1.txt");
-    println!("{:?}", part1(input));
-    println!("{:?}", part2(input));
+
+    aoc::run::Day::new("2023/day4")
+        .input("input1", input)
+        .part(1, part1)
+        .part(2, part2)
+        .run();
 }
 
 fn part1(input: &str) -> Option<u32> {
@@ -0,0 +1,94 @@
+use std::fmt::Write;
+
+use aoc::generate::Rng;
+
+const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+/// the real numbers fit in 32 bits
+const MAX: u64 = 1 << 32;
+
+/// `size` seed ranges, and `size` ranges in every map. like in the real almanac the source
+/// ranges of a map don't overlap, and they leave gaps
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut input = String::from("seeds:");
+
+    for _ in 0..size {
+        let start = rng.below(MAX);
+        let len = rng.range(1..(MAX - start).min(1 << 28) + 1);
+        write!(input, " {start} {len}").unwrap();
+    }
+    input.push('\n');
+
+    for map in MAPS {
+        // every other interval between the bounds is a source range, the others are gaps
+        let mut bounds = (0..2 * size).map(|_| rng.below(MAX)).collect::<Vec<_>>();
+        bounds.sort_unstable();
+
+        let mut ranges = bounds
+            .chunks(2)
+            .filter(|pair| pair[0] < pair[1])
+            .map(|pair| {
+                let len = pair[1] - pair[0];
+                format!("{} {} {len}\n", rng.below(MAX - len + 1), pair[0])
+            })
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut ranges);
+
+        write!(input, "\n{map} map:\n{}", ranges.concat()).unwrap();
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{map_seed, parse_mapping, part1, part2};
+    use aoc::generate;
+
+    #[test]
+    fn generated_almanacs() {
+        for (seed, input) in generate::inputs(input, 50) {
+            let location = part2(&input).unwrap();
+
+            let mut groups = input.split("\n\n");
+            let seeds = groups
+                .next()
+                .unwrap()
+                .split(' ')
+                .skip(1)
+                .map(|n| n.trim().parse::<u64>().unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(seeds.len(), 2 * 50, "seed {seed}");
+
+            // the source ranges of a map are apart
+            let mappings = groups.map(parse_mapping).collect::<Vec<_>>();
+            assert_eq!(mappings.len(), MAPS.len(), "seed {seed}");
+            for mapping in &mappings {
+                let mut sources = mapping.iter().map(|(src, _)| src).collect::<Vec<_>>();
+                sources.sort_unstable_by_key(|src| src.start);
+                assert!(
+                    sources.windows(2).all(|w| w[0].end <= w[1].start),
+                    "seed {seed}"
+                );
+            }
+
+            // the start of every seed range is a seed, so nothing can be lower than them
+            for &start in seeds.iter().step_by(2) {
+                let mapped = mappings.iter().fold(start, map_seed);
+                assert!(location <= mapped, "seed {seed}");
+            }
+
+            assert!(part1(&input).is_some(), "seed {seed}");
+        }
+    }
+}
@@ -1,9 +1,17 @@
+#[cfg(test)]
+mod examples;
+mod generate;
+
 use std::ops::Range;
 
 fn main() {
-    let input = include_str!("input1.txt");
-    println!("{:?}", part1(input));
-    println!("{:?}", part2(input));
+    let input = aoc::generate::input_or(include_str!("input1.txt"), generate::input);
+
+    aoc::run::Day::new("2023/day5")
+        .input("input1", input)
+        .part(1, part1)
+        .part(2, part2)
+        .run();
 }
 
 fn parse_seeds(s: &str) -> Option<Vec<u64>> {
@@ -1,9 +1,16 @@
+#[cfg(test)]
+mod examples;
+
 use std::iter::zip;
 
 fn main() {
     let input = include_str!("input1.txt");
-    println!("{:?}", part1(input));
-    println!("{:?}", part2(input));
+
+    aoc::run::Day::new("2023/day6")
+        .input("input1", input)
+        .part(1, part1)
+        .part(2, part2)
+        .run();
 }
 
 fn parse_input_line(line: &str) -> Option<Vec<u32>> {
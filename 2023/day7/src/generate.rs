@@ -0,0 +1,97 @@
+use std::{collections::HashSet, fmt::Write};
+
+use aoc::generate::Rng;
+
+const CARDS: &[u8] = b"23456789TJQKA";
+
+/// how many different hands there are
+const HANDS: usize = CARDS.len().pow(5);
+
+/// the `i`th hand, in the order of the cards in [`CARDS`]
+fn nth_hand(mut i: usize) -> String {
+    let mut hand = String::with_capacity(5);
+    for _ in 0..5 {
+        hand.push(CARDS[i % CARDS.len()] as char);
+        i /= CARDS.len();
+    }
+    hand
+}
+
+/// `size` different hands (at most 13^5 of them) with bids up to 1000.
+/// a hand is made from a few kinds of cards, so that all the types come up. past half of all
+/// the hands, drawing until enough are new takes too long, so they're taken from all of them
+/// shuffled instead
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let size = size.min(HANDS);
+
+    let hands = if size > HANDS / 2 {
+        let mut hands = (0..HANDS).map(nth_hand).collect::<Vec<_>>();
+        rng.shuffle(&mut hands);
+        hands.truncate(size);
+        hands
+    } else {
+        let mut seen = HashSet::new();
+        let mut hands = Vec::with_capacity(size);
+
+        while hands.len() < size {
+            let kinds = (0..rng.range(1..6))
+                .map(|_| *rng.choose(CARDS))
+                .collect::<Vec<_>>();
+            let hand = (0..5)
+                .map(|_| *rng.choose(&kinds) as char)
+                .collect::<String>();
+
+            if seen.insert(hand.clone()) {
+                hands.push(hand);
+            }
+        }
+        hands
+    };
+
+    let mut input = String::new();
+    for hand in hands {
+        writeln!(input, "{hand} {}", rng.range(1..1001)).unwrap();
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+    use aoc::generate;
+
+    /// `size` different hands of 5 cards, with bids from 1 to 1000
+    fn check_hands(input: &str, size: usize) {
+        let mut hands = HashSet::new();
+
+        for line in input.lines() {
+            let (hand, bid) = line.split_once(' ').unwrap();
+            assert!(
+                hand.len() == 5 && hand.bytes().all(|c| CARDS.contains(&c)),
+                "{line}"
+            );
+            assert!((1..=1000).contains(&bid.parse::<u32>().unwrap()), "{line}");
+            assert!(hands.insert(hand), "{hand} twice");
+        }
+
+        assert_eq!(hands.len(), size);
+    }
+
+    #[test]
+    fn generated_hands() {
+        for (seed, input) in generate::inputs(input, 1000) {
+            check_hands(&input, 1000);
+
+            assert!(part1(&input).is_some(), "seed {seed}");
+            assert!(part2(&input).is_some(), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn every_hand_at_most() {
+        check_hands(&input(&mut Rng::new(0), HANDS / 2 + 1), HANDS / 2 + 1);
+        check_hands(&input(&mut Rng::new(0), 2 * HANDS), HANDS);
+    }
+}
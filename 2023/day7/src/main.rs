@@ -1,7 +1,16 @@
+#[cfg(test)]
+mod examples;
+mod generate;
+#[cfg(test)]
+mod properties;
+#[cfg(test)]
+mod variants;
+
 use std::cmp::Ordering;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
+use std::fmt;
 
-#[derive(Eq, PartialEq, Hash, Copy, Clone)]
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
 #[repr(u8)]
 enum Card {
     Digit(char),
@@ -73,7 +82,7 @@ impl From<Card> for char {
     }
 }
 
-#[derive(Eq, PartialEq, Clone, Copy)]
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
 #[repr(u8)]
 enum Hand {
     HighCard(Card),
@@ -109,8 +118,8 @@ impl Hand {
                 _ => __self,
             },
             HighCard(_) => match self.cmp(other) {
-                Ordering::Greater => __self,
-                _ => __other,
+                Ordering::Less => __other,
+                _ => __self,
             },
         }
     }
@@ -118,7 +127,7 @@ impl Hand {
     fn try_from_cards(cards: &[Card]) -> Option<Hand> {
         use Hand::*;
         let mut current_best: Option<Hand> = None;
-        let mut occurrences = HashMap::new();
+        let mut occurrences: Vec<(Card, usize)> = Vec::new();
 
         // in part2 J can assume another value, and i need to consider that one
         let real_cards = cards
@@ -129,13 +138,17 @@ impl Hand {
             })
             .collect::<Vec<_>>();
 
-        real_cards.iter().for_each(|c| {
-            occurrences
-                .entry(c)
-                .or_insert(real_cards.iter().filter(|&c2| c2 == c).count());
+        real_cards.iter().for_each(|&c| {
+            if !occurrences.iter().any(|&(c2, _)| c2 == c) {
+                occurrences.push((c, real_cards.iter().filter(|&&c2| c2 == c).count()));
+            }
         });
 
-        for (&c, count) in occurrences {
+        // the most common cards first, the highest first among those, so that the same cards
+        // always make the same hand
+        occurrences.sort_by(|(c1, n1), (c2, n2)| n2.cmp(n1).then(c2.cmp_canonical_order(c1)));
+
+        for (c, count) in occurrences {
             let next = match count {
                 5 => FiveOfAKind(c),
                 4 => FourOfAKind(c),
@@ -197,6 +210,33 @@ impl Hand {
     fn try_from_part2(cards: &[Card]) -> Option<Hand> {
         Self::try_joker_values(0, cards)
     }
+
+    // without trying them all: the jokers are best as the card there's the most of
+    #[cfg(test)]
+    fn try_from_part2_direct(cards: &[Card]) -> Option<Hand> {
+        use std::collections::HashMap;
+
+        let mut occurrences: HashMap<Card, usize> = HashMap::new();
+        for &c in cards.iter().filter(|c| !matches!(c, Card::J(_))) {
+            *occurrences.entry(c).or_default() += 1;
+        }
+
+        // in case i have JJJJJ
+        let most = occurrences
+            .into_iter()
+            .max_by_key(|&(_, count)| count)
+            .map_or(Card::A, |(c, _)| c);
+
+        let cards = cards
+            .iter()
+            .map(|&c| match c {
+                Card::J(_) => Card::J(most.into()),
+                _ => c,
+            })
+            .collect::<Vec<_>>();
+
+        Self::try_from_cards(&cards)
+    }
 }
 
 // it needs to ignore the data, and only use the discriminant
@@ -212,6 +252,7 @@ impl PartialOrd for Hand {
     }
 }
 
+#[derive(Debug, PartialEq)]
 struct HandData {
     cards: Vec<Card>,
     hand: Hand,
@@ -265,25 +306,44 @@ impl HandData {
             .chars()
             .map(|c| c.try_into().ok())
             .collect::<Option<Vec<_>>>()?;
+        if cards.len() != 5 {
+            return None;
+        }
+
         let hand = hand_try_from(&cards[..])?;
         let bid = data.next()?.parse().ok()?;
 
+        // nothing after the bid
+        if data.next().is_some() {
+            return None;
+        }
+
         Some(HandData { cards, hand, bid })
     }
 
     fn try_from_part1(line: &str) -> Option<HandData> {
         Self::try_from_str(line, Hand::try_from_part1)
     }
+}
+
+impl fmt::Display for HandData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &card in &self.cards {
+            write!(f, "{}", char::from(card))?;
+        }
 
-    fn try_from_part2(line: &str) -> Option<HandData> {
-        Self::try_from_str(line, Hand::try_from_part2)
+        write!(f, " {}", self.bid)
     }
 }
 
 fn main() {
-    let input = include_str!("input1.txt");
-    println!("{:?}", part1(input));
-    println!("{:?}", part2(input));
+    let input = aoc::generate::input_or(include_str!("input1.txt"), generate::input);
+
+    aoc::run::Day::new("2023/day7")
+        .input("input1", input)
+        .part(1, part1)
+        .part(2, part2)
+        .run();
 }
 
 fn part1(input: &str) -> Option<u32> {
@@ -303,9 +363,13 @@ fn part1(input: &str) -> Option<u32> {
 }
 
 fn part2(input: &str) -> Option<u32> {
+    part2_with(input, Hand::try_from_part2)
+}
+
+fn part2_with(input: &str, hand: fn(&[Card]) -> Option<Hand>) -> Option<u32> {
     let mut hands = input
         .lines()
-        .map(HandData::try_from_part2)
+        .map(|line| HandData::try_from_str(line, hand))
         .collect::<Option<Vec<_>>>()?;
 
     hands.sort_by(HandData::cmp_part2);
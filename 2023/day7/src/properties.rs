@@ -0,0 +1,46 @@
+use aoc::{generate::Rng, prop};
+
+use crate::{Card, Hand, HandData};
+
+fn hand(rng: &mut Rng) -> HandData {
+    let cards = (0..5)
+        .map(|_| Card::try_from(*rng.choose(b"23456789TJQKA") as char).unwrap())
+        .collect::<Vec<_>>();
+
+    HandData {
+        hand: Hand::try_from_part1(&cards).unwrap(),
+        cards,
+        bid: rng.range(1..1001) as u32,
+    }
+}
+
+#[test]
+fn hands_round_trip() {
+    prop::check(hand, |hand| {
+        HandData::try_from_str(&hand.to_string(), Hand::try_from_part1).as_ref() == Some(hand)
+    });
+}
+
+#[test]
+fn corrupted_hands_are_rejected() {
+    let corrupted = |rng: &mut Rng| {
+        let line = hand(rng).to_string();
+        prop::corrupt(rng, &line)
+    };
+
+    // a doubled or deleted digit of the bid is still a hand
+    prop::check(corrupted, |line| {
+        HandData::try_from_str(line, Hand::try_from_part1)
+            .is_none_or(|hand| hand.cards.len() == 5 && hand.to_string() == *line)
+    });
+}
+
+#[test]
+fn hands_are_five_cards_and_a_bid() {
+    for line in ["2345 1", "234567 1", "23456 1 2", "23456", "23456 "] {
+        assert!(
+            HandData::try_from_str(line, Hand::try_from_part1).is_none(),
+            "{line}"
+        );
+    }
+}
@@ -0,0 +1,21 @@
+use aoc::diff;
+
+use crate::{generate, part2_with, Hand};
+
+#[test]
+fn part2_variants() {
+    diff::assert_agree(
+        &[include_str!("input0.txt"), include_str!("input1.txt")],
+        generate::input,
+        500,
+        &[
+            ("every joker value", &|input| {
+                part2_with(input, Hand::try_from_part2)
+            }),
+            ("jokers as the most common card", &|input| {
+                part2_with(input, Hand::try_from_part2_direct)
+            }),
+        ],
+        diff::remove_lines,
+    );
+}
@@ -0,0 +1,30 @@
+const PART1_EXAMPLE: &str = "\
+RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)
+";
+
+const PART2_EXAMPLE: &str = "\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+";
+
+aoc::examples! {
+    part1_input0: part1(include_str!("input0.txt")) => Some(6),
+    part1_example: part1(PART1_EXAMPLE) => Some(2),
+    part2_example: part2(PART2_EXAMPLE) => Some(6),
+}
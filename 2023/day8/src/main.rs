@@ -1,5 +1,11 @@
-use std::collections::HashMap;
+#[cfg(test)]
+mod examples;
+#[cfg(test)]
+mod properties;
 
+use std::{collections::HashMap, fmt};
+
+#[derive(Debug, PartialEq, Eq)]
 struct Node<'a> {
     name: &'a str,
     links: (&'a str, &'a str),
@@ -9,14 +15,27 @@ impl<'a> TryFrom<&'a str> for Node<'a> {
     type Error = ();
 
     fn try_from(value: &'a str) -> Result<Self, Self::Error> {
-        let mut data = value.split('=');
-        let name = data.next().ok_or(())?.trim();
-        let mut links = data.next().ok_or(())?.trim().split(',');
-        let left = links.next().ok_or(())?.trim();
-        let right = links.next().ok_or(())?.trim();
-        let links = (&left[1..], &right[..right.len() - 1]);
-
-        Ok(Node { name, links })
+        let is_name = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_alphanumeric());
+
+        let (name, links) = value.split_once(" = ").ok_or(())?;
+        let links = links.strip_prefix('(').ok_or(())?;
+        let links = links.strip_suffix(')').ok_or(())?;
+        let (left, right) = links.split_once(", ").ok_or(())?;
+
+        if ![name, left, right].into_iter().all(is_name) {
+            return Err(());
+        }
+
+        Ok(Node {
+            name,
+            links: (left, right),
+        })
+    }
+}
+
+impl fmt::Display for Node<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = ({}, {})", self.name, self.links.0, self.links.1)
     }
 }
 
@@ -43,13 +62,66 @@ fn gcd(mut a: u64, mut b: u64) -> u64 {
 }
 
 fn lcm(a: u64, b: u64) -> u64 {
-    (a * b) / gcd(a, b)
+    (a / gcd(a, b)) * b
+}
+
+/// combines `t = a1 (mod n1)` and `t = a2 (mod n2)` into a single `t = a (mod lcm(n1, n2))`.
+/// the moduli don't need to be coprime, but then there might be no solution
+fn crt((a1, n1): (u64, u64), (a2, n2): (u64, u64)) -> Option<(u64, u64)> {
+    let g = gcd(n1, n2);
+
+    if a1 % g != a2 % g {
+        return None;
+    }
+
+    // t = a1 + n1 * k, with n1 * k = a2 - a1 (mod n2), solved through the inverse of n1/g mod n2/g
+    let m = (n2 / g) as i128;
+    let (mut old_r, mut r) = ((n1 / g) as i128 % m, m);
+    let (mut old_s, mut s) = (1i128, 0i128);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+    }
+
+    let diff = (a2 as i128 - a1 as i128) / g as i128;
+    let k = (diff * old_s).rem_euclid(m);
+    let n = lcm(n1, n2);
+    let a = (a1 as i128 + n1 as i128 * k).rem_euclid(n as i128);
+
+    Some((a as u64, n))
+}
+
+/// steps after which a ghost is on a node ending with `Z`
+struct Ghost {
+    /// before reaching its cycle, which starts after `prefix` steps
+    prefix_hits: Vec<u64>,
+    prefix: u64,
+    /// during one lap of its cycle. they repeat every `length` steps
+    cycle_hits: Vec<u64>,
+    length: u64,
+}
+
+impl Ghost {
+    fn is_on_z(&self, step: u64) -> bool {
+        if step < self.prefix {
+            self.prefix_hits.contains(&step)
+        } else {
+            self.cycle_hits
+                .iter()
+                .any(|&hit| (step - hit).is_multiple_of(self.length))
+        }
+    }
 }
 
 fn main() {
     let input = include_str!("input1.txt");
-    println!("{:?}", part1(input));
-    println!("{:?}", part2(input));
+
+    aoc::run::Day::new("2023/day8")
+        .input("input1", input)
+        .part(1, part1)
+        .part(2, part2)
+        .run();
 }
 
 fn part1(input: &str) -> Option<u64> {
@@ -79,41 +151,74 @@ fn part1(input: &str) -> Option<u64> {
 
 fn part2(input: &str) -> Option<u64> {
     let mut data = input.lines();
-    let instructions = data.next()?;
+    let instructions = data.next()?.as_bytes();
     let nodes = parse_nodes(data.skip(1))?;
 
-    let mut curr = nodes
-        .iter()
-        .filter_map(|(&k, node)| {
-            if k.ends_with('A') {
-                Some((node, 0))
-            } else {
-                None
+    let valid_links = nodes
+        .values()
+        .all(|node| nodes.contains_key(node.links.0) && nodes.contains_key(node.links.1));
+    if instructions.is_empty() || !instructions.iter().all(|c| b"LR".contains(c)) || !valid_links {
+        return None;
+    }
+
+    // a ghost's state is its node and where it is in the instructions
+    let next = |&(name, idx): &(&str, usize)| {
+        let node = &nodes[name];
+        let name = match instructions[idx] {
+            b'L' => node.links.0,
+            _ => node.links.1,
+        };
+
+        (name, (idx + 1) % instructions.len())
+    };
+
+    let ghosts = nodes
+        .keys()
+        .filter(|name| name.ends_with('A'))
+        .map(|&name| {
+            let (cycle, history) = aoc::cycle::hashed((name, 0), next);
+            let (prefix, length) = (cycle.prefix as u64, cycle.length as u64);
+
+            let (prefix_hits, cycle_hits) = history
+                .iter()
+                .enumerate()
+                .filter(|(_, (name, _))| name.ends_with('Z'))
+                .map(|(step, _)| step as u64)
+                .partition(|&step| step < prefix);
+
+            Ghost {
+                prefix_hits,
+                prefix,
+                cycle_hits,
+                length,
             }
         })
         .collect::<Vec<_>>();
-    let mut count = 0;
 
-    for (step, instruction) in instructions.chars().cycle().enumerate() {
-        for (node, min_steps) in curr.iter_mut() {
-            *node = match instruction {
-                'L' => nodes.get(node.links.0)?,
-                'R' => nodes.get(node.links.1)?,
-                _ => return None,
-            };
-
-            if node.name.ends_with('Z') {
-                *min_steps = (step + 1) as u64;
-                count += 1;
-            }
-        }
+    // until every ghost is in its cycle, there's nothing better than checking step by step
+    let settled = ghosts.iter().map(|ghost| ghost.prefix).max()?.max(1);
 
-        if count == curr.len() {
-            break;
-        }
+    if let Some(step) = (1..settled).find(|&step| ghosts.iter().all(|ghost| ghost.is_on_z(step))) {
+        return Some(step);
     }
 
-    let lcm = curr.iter().fold(1, |acc, &(_, steps)| lcm(acc, steps));
+    // from then on, every combination of one hit per cycle is a system of congruences
+    let mut systems = vec![(0, 1)];
+
+    for ghost in &ghosts {
+        systems = systems
+            .iter()
+            .flat_map(|&system| {
+                ghost
+                    .cycle_hits
+                    .iter()
+                    .filter_map(move |&hit| crt(system, (hit % ghost.length, ghost.length)))
+            })
+            .collect();
+    }
 
-    Some(lcm)
+    systems
+        .into_iter()
+        .map(|(a, n)| a + n * settled.saturating_sub(a).div_ceil(n))
+        .min()
 }
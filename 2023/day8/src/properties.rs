@@ -0,0 +1,43 @@
+use aoc::{generate::Rng, prop};
+
+use crate::Node;
+
+/// the names of a node and its links, in the alphabet of the real ones
+fn names(rng: &mut Rng) -> [String; 3] {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+    [(); 3].map(|_| (0..3).map(|_| *rng.choose(ALPHABET) as char).collect())
+}
+
+#[test]
+fn nodes_round_trip() {
+    prop::check(names, |[name, left, right]| {
+        let node = Node {
+            name,
+            links: (left, right),
+        };
+
+        Node::try_from(node.to_string().as_str()) == Ok(node)
+    });
+}
+
+#[test]
+fn corrupted_nodes_are_rejected() {
+    let corrupted = |rng: &mut Rng| {
+        let [name, left, right] = names(rng);
+        let line = Node {
+            name: &name,
+            links: (&left, &right),
+        }
+        .to_string();
+
+        prop::corrupt(rng, &line)
+    };
+
+    // a deleted or doubled character of a name is still a name
+    prop::check(corrupted, |line| {
+        Node::try_from(line.as_str())
+            .ok()
+            .is_none_or(|node| node.to_string() == *line)
+    });
+}
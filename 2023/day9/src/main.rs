@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod examples;
+
 fn derive(v: &[i64]) -> Vec<i64> {
     v.iter()
         .zip(v.iter().skip(1))
@@ -33,8 +36,12 @@ fn parse_line(s: &str) -> Vec<i64> {
 
 fn main() {
     let input = include_str!("input1.txt");
-    println!("{:?}", part1(input));
-    println!("{:?}", part2(input));
+
+    aoc::run::Day::new("2023/day9")
+        .input("input1", input)
+        .part(1, part1)
+        .part(2, part2)
+        .run();
 }
 
 fn part1(input: &str) -> Option<i64> {
@@ -1,13 +1,19 @@
+#[cfg(test)]
+mod examples;
+
 use std::{collections::HashMap, iter};
 
 fn main() {
-    let input = include_str!("input0.txt");
-    println!("{:?}", part1(input));
-    println!("{:?}", part2(input));
+    let input0 = include_str!("input0.txt");
+    let input1 = include_str!("input1.txt");
 
-    let input = include_str!("input1.txt");
-    println!("{:?}", part1(input));
-    println!("{:?}", part2(input));
+    aoc::run::Day::new("2024/day1")
+        .input("input0", input0)
+        .input("input1", input1)
+        .parse("parse", parse_input)
+        .part(1, part1)
+        .part(2, part2)
+        .run();
 }
 
 fn parse_input(input: &str) -> Option<(Vec<u32>, Vec<u32>)> {
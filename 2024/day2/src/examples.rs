@@ -0,0 +1,4 @@
+aoc::examples! {
+    part1_input0: part1(include_str!("input0.txt")) => Some(2),
+    part2_input0: part2(include_str!("input0.txt")) => Some(4),
+}
@@ -0,0 +1,27 @@
+use aoc::generate::Rng;
+
+/// `size` reports of 5 to 8 levels. most of them are mostly safe, going one way by 1 to 3,
+/// but a few levels are off, to get rows that the dampener fixes and rows it can't
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let len = rng.range(5..9);
+            let up = rng.chance(0.5);
+            let mut level = rng.range(40..80) as i64;
+            let mut levels = Vec::with_capacity(len as usize);
+
+            for _ in 0..len {
+                levels.push(level.to_string());
+
+                let step = if rng.chance(0.1) {
+                    rng.range(0..8) as i64 - 4
+                } else {
+                    rng.range(1..4) as i64
+                };
+                level += if up { step } else { -step };
+            }
+
+            levels.join(" ") + "\n"
+        })
+        .collect()
+}
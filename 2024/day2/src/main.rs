@@ -1,11 +1,20 @@
+#[cfg(test)]
+mod examples;
+mod generate;
+#[cfg(test)]
+mod variants;
+
 fn main() {
-    let input = include_str!("input0.txt");
-    println!("{:?}", part1(input));
-    println!("{:?}", part2(input));
+    let input0 = include_str!("input0.txt");
+    let input1 = aoc::generate::input_or(include_str!("input1.txt"), generate::input);
 
-    let input = include_str!("input1.txt");
-    println!("{:?}", part1(input));
-    println!("{:?}", part2(input));
+    aoc::run::Day::new("2024/day2")
+        .input("input0", input0)
+        .input("input1", input1)
+        .parse("parse", parse_input)
+        .part(1, part1)
+        .part(2, part2)
+        .run();
 }
 
 fn parse_input(input: &str) -> Option<Vec<Vec<u32>>> {
@@ -22,18 +31,37 @@ fn parse_input(input: &str) -> Option<Vec<Vec<u32>>> {
 fn check(row: &[u32]) -> bool {
     let mut dir = None;
 
-    row.iter()
-        .zip(row.iter().skip(1))
-        .map(|(&curr, &next)| {
-            let diff: i32 = curr as i32 - next as i32;
-            let dist = diff.unsigned_abs();
-            let sign = diff.signum();
-            let safe = dir.unwrap_or(sign) == sign && (1..=3).contains(&dist);
-            dir = Some(sign);
+    row.iter().zip(row.iter().skip(1)).all(|(&curr, &next)| {
+        let diff: i32 = curr as i32 - next as i32;
+        let dist = diff.unsigned_abs();
+        let sign = diff.signum();
+        let safe = dir.unwrap_or(sign) == sign && (1..=3).contains(&dist);
+        dir = Some(sign);
+
+        safe
+    })
+}
+
+/// the first level that doesn't go in `sign` direction, by 1 to 3, from the previous one
+fn first_unsafe(row: &[u32], sign: i32) -> Option<usize> {
+    (1..row.len()).find(|&i| {
+        let diff = row[i] as i32 - row[i - 1] as i32;
+        diff.signum() != sign || diff.unsigned_abs() > 3
+    })
+}
 
-            safe
+/// any level that is removed to fix the row must be one of the two around the first unsafe step,
+/// or that step would still be there
+fn check_dampened(row: &[u32]) -> bool {
+    [1, -1]
+        .into_iter()
+        .any(|sign| match first_unsafe(row, sign) {
+            None => true,
+            Some(i) => [i - 1, i].into_iter().any(|removed| {
+                let split = [&row[..removed], &row[removed + 1..]].concat();
+                first_unsafe(&split, sign).is_none()
+            }),
         })
-        .all(|b| b)
 }
 
 fn part1(input: &str) -> Option<u32> {
@@ -42,10 +70,17 @@ fn part1(input: &str) -> Option<u32> {
     Some(rows.iter().fold(0, |acc, row| acc + check(row) as u32))
 }
 
-/// TODO: without bruteforce
 fn part2(input: &str) -> Option<u32> {
     let rows = parse_input(input)?;
 
+    Some(rows.iter().filter(|row| check_dampened(row)).count() as u32)
+}
+
+/// tries removing every level until the row is safe
+#[cfg(test)]
+fn part2_bruteforce(input: &str) -> Option<u32> {
+    let rows = parse_input(input)?;
+
     let total = rows.iter().fold(0, |acc, row| {
         let mut safe = check(row);
 
@@ -63,4 +98,4 @@ fn part2(input: &str) -> Option<u32> {
     });
 
     Some(total)
-}
\ No newline at end of file
+}
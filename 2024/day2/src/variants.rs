@@ -0,0 +1,14 @@
+use aoc::diff;
+
+use crate::{generate, part2, part2_bruteforce};
+
+#[test]
+fn part2_variants() {
+    diff::assert_agree(
+        &[include_str!("input0.txt"), include_str!("input1.txt")],
+        generate::input,
+        100,
+        &[("part2", &part2), ("part2_bruteforce", &part2_bruteforce)],
+        |input| [diff::remove_lines(input), diff::remove_fields(input, ' ')].concat(),
+    );
+}
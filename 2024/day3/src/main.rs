@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod examples;
+
 fn parse_mul(input: &str) -> Option<(usize, (u32, u32))> {
     let start = "mul(";
     let end = ")";
@@ -92,12 +95,15 @@ fn part2(input: &str) -> Option<u32> {
 }
 
 fn main() {
-    let input = include_str!("input0.txt");
-    println!("{:?}", part1(input));
-    let input = include_str!("input0_2.txt");
-    println!("{:?}", part2(input));
-
-    let input = include_str!("input1.txt");
-    println!("{:?}", part1(input));
-    println!("{:?}", part2(input));
+    let input0 = include_str!("input0.txt");
+    let input0_2 = include_str!("input0_2.txt");
+    let input1 = include_str!("input1.txt");
+
+    aoc::run::Day::new("2024/day3")
+        .input_for(1, "input0", input0)
+        .input_for(2, "input0_2", input0_2)
+        .input("input1", input1)
+        .part(1, part1)
+        .part(2, part2)
+        .run();
 }
@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod examples;
+
 use std::cmp::{max, min};
 
 fn parse_input(input: &str) -> Option<Vec<&[u8]>> {
@@ -35,7 +38,7 @@ fn search_pt1(matrix: &[&[u8]]) -> Option<u32> {
 
     for y in 0..len {
         haystack.clear();
-        (0..len).for_each(|x| haystack.push(matrix[x][y]));
+        haystack.extend(matrix.iter().map(|row| row[y]));
 
         total += count_occourrences(&haystack, forwards, backwards);
     }
@@ -146,9 +149,11 @@ fn main() {
     let input0 = include_str!("input0.txt");
     let input1 = include_str!("input1.txt");
 
-    println!("{:?}", part1(input0));
-    println!("{:?}", part1(input1));
-
-    println!("{:?}", part2(input0));
-    println!("{:?}", part2(input1));
+    aoc::run::Day::new("2024/day4")
+        .input("input0", input0)
+        .input("input1", input1)
+        .parse("parse", parse_input)
+        .part(1, part1)
+        .part(2, part2)
+        .run();
 }
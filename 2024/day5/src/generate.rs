@@ -0,0 +1,40 @@
+use aoc::generate::Rng;
+
+/// `size` pages numbered from 10, and `size` updates of an odd number of them, up to 23.
+/// there's a rule between every two pages, so an update has only one right order,
+/// and about half of the updates are in it
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+
+    // the right order
+    let mut pages = (10..10 + size as u32).collect::<Vec<_>>();
+    rng.shuffle(&mut pages);
+
+    let mut rules = Vec::with_capacity(size * (size - 1) / 2);
+    for (i, pred) in pages.iter().enumerate() {
+        for succ in &pages[i + 1..] {
+            rules.push(format!("{pred}|{succ}\n"));
+        }
+    }
+    rng.shuffle(&mut rules);
+
+    let max_len = size.min(23) as u64;
+    let updates = (0..size).map(|_| {
+        let len = 2 * rng.below(max_len.div_ceil(2)) as usize + 1;
+
+        let mut picked = (0..size).collect::<Vec<_>>();
+        rng.shuffle(&mut picked);
+        picked.truncate(len);
+        if rng.chance(0.5) {
+            picked.sort_unstable();
+        }
+
+        let update = picked
+            .iter()
+            .map(|&i| pages[i].to_string())
+            .collect::<Vec<_>>();
+        update.join(",") + "\n"
+    });
+
+    rules.concat() + "\n" + &updates.collect::<String>()
+}
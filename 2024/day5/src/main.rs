@@ -1,6 +1,12 @@
+#[cfg(test)]
+mod examples;
+mod generate;
+#[cfg(test)]
+mod variants;
+
 use std::{
     cmp::Ordering,
-    collections::{HashMap, HashSet, VecDeque},
+    collections::{HashMap, HashSet},
 };
 
 #[allow(clippy::type_complexity)]
@@ -86,51 +92,28 @@ fn handmade_sort(rules: &HashMap<u32, HashSet<u32>>, update: &[u32]) -> Option<V
 }
 
 #[allow(unused)]
-/// Kahn's algorithm for DAGs (needs to count only the stuff in `update`, not the whole `graph`)
+/// Kahn's algorithm for DAGs (needs to count only the stuff in `update`, not the whole `graph`).
+/// returns `None` if the rules between the pages of `update` have a cycle
 fn topological_sort(graph: &HashMap<u32, HashSet<u32>>, update: &[u32]) -> Option<Vec<u32>> {
-    let mut successors_map = HashMap::new();
-
-    let mut edges = update.iter().map(|&p| (p, 0)).collect::<HashMap<_, _>>();
-
-    for (&node, dependencies) in graph {
-        for &dep in dependencies {
-            if update.contains(&node) && update.contains(&dep) {
-                successors_map
-                    .entry(node)
-                    .or_insert_with(Vec::new)
-                    .push(dep);
-                *edges.entry(dep).or_insert(0) += 1;
-            }
-        }
-    }
-
-    let mut queue = edges
-        .iter()
-        .filter(|(_, &count)| count == 0)
-        .map(|(&node, _)| node)
-        .collect::<VecDeque<_>>();
-
-    let mut sorted = Vec::new();
-
-    while let Some(node) = queue.pop_front() {
-        sorted.push(node);
+    let successors = |page: &u32| {
+        graph
+            .get(page)
+            .into_iter()
+            .flatten()
+            .filter(|succ| update.contains(succ))
+            .copied()
+    };
+
+    aoc::graph::topological_sort(update.iter().copied(), successors).ok()
+}
 
-        if let Some(successors) = successors_map.get(&node) {
-            for &succ in successors {
-                let edge = edges.get_mut(&succ)?;
-                *edge -= 1;
+type Sort = fn(&HashMap<u32, HashSet<u32>>, &[u32]) -> Option<Vec<u32>>;
 
-                if *edge == 0 {
-                    queue.push_back(succ);
-                }
-            }
-        }
-    }
-
-    Some(sorted)
+fn part2(input: &str) -> Option<u32> {
+    part2_with(input, handmade_sort)
 }
 
-fn part2(input: &str) -> Option<u32> {
+fn part2_with(input: &str, sort: Sort) -> Option<u32> {
     let (rules, updates) = parse_input(input)?;
 
     let total = updates
@@ -141,9 +124,7 @@ fn part2(input: &str) -> Option<u32> {
                     // a normal sort won't work because `rules` are not transitive
                     // i initially took a "direct" approach with `handmade_sort`
                     // but a "scientific" one would be to see the rules as a DAG and use `topological_sort`
-                    
-                    //let sorted = topological_sort(&rules, update);
-                    let sorted = handmade_sort(&rules, update);
+                    let sorted = sort(&rules, update);
 
                     sorted.and_then(|sorted| sorted.get(sorted.len() / 2).copied())
                 }
@@ -157,11 +138,13 @@ fn part2(input: &str) -> Option<u32> {
 
 fn main() {
     let input0 = include_str!("input0.txt");
-    let input1 = include_str!("input1.txt");
-
-    println!("{:?}", part1(input0));
-    println!("{:?}", part1(input1));
-
-    println!("{:?}", part2(input0));
-    println!("{:?}", part2(input1));
+    let input1 = aoc::generate::input_or(include_str!("input1.txt"), generate::input);
+
+    aoc::run::Day::new("2024/day5")
+        .input("input0", input0)
+        .input("input1", input1)
+        .parse("parse", parse_input)
+        .part(1, part1)
+        .part(2, part2)
+        .run();
 }
@@ -0,0 +1,19 @@
+use aoc::diff;
+
+use crate::{generate, handmade_sort, part2_with, topological_sort};
+
+#[test]
+fn part2_variants() {
+    diff::assert_agree(
+        &[include_str!("input0.txt"), include_str!("input1.txt")],
+        generate::input,
+        60,
+        &[
+            ("handmade_sort", &|input| part2_with(input, handmade_sort)),
+            ("topological_sort", &|input| {
+                part2_with(input, topological_sort)
+            }),
+        ],
+        |input| [diff::remove_lines(input), diff::remove_fields(input, ',')].concat(),
+    );
+}
@@ -0,0 +1,24 @@
+const WALK_INPUT0: &str = "\
+....#.....
+....XXXXX#
+....X...X.
+..#.X...X.
+..XXXXX#X.
+..X.X.X.X.
+.#XXXXXXX.
+.XXXXXXX#.
+#XXXXXXX..
+......#X..";
+
+fn last_frame(input: &str) -> Option<String> {
+    let mut animation = Animation::headless();
+    animate(input, &mut animation)?;
+
+    animation.last_frame().map(ToString::to_string)
+}
+
+aoc::examples! {
+    part1_input0: part1(include_str!("input0.txt")) => Some(41),
+    part2_input0: part2(include_str!("input0.txt")) => Some(6),
+    walk_input0: last_frame(include_str!("input0.txt")) => Some(WALK_INPUT0.to_owned()),
+}
@@ -0,0 +1,78 @@
+use aoc::generate::Rng;
+
+use super::{EMPTY_BLOCK, START_BLOCK, WALL_BLOCK};
+
+/// a `size` × `size` lab with obstructions on a tenth of the cells, and the guard facing up
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut grid = (0..size * size)
+        .map(|_| {
+            if rng.chance(0.1) {
+                WALL_BLOCK
+            } else {
+                EMPTY_BLOCK
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let guard = rng.below(grid.len() as u64) as usize;
+    grid[guard] = START_BLOCK;
+
+    grid.chunks(size)
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::part1;
+    use aoc::generate;
+
+    /// the straightforward way: walk, and stop when out or when a step is taken a second time
+    fn walk(input: &str) -> u32 {
+        let grid = input.lines().map(|l| l.as_bytes()).collect::<Vec<_>>();
+        let cell = |(y, x): (isize, isize)| grid.get(y as usize)?.get(x as usize).copied();
+
+        let start = (0..grid.len())
+            .flat_map(|y| (0..grid[y].len()).map(move |x| (y as isize, x as isize)))
+            .find(|&pos| cell(pos) == Some(START_BLOCK as u8))
+            .unwrap();
+
+        let (mut pos, mut dir) = (start, (-1, 0));
+        let mut steps = HashSet::new();
+        let mut visited = HashSet::from([pos]);
+
+        for _ in 0..4 * grid.len() * grid.len() * 4 {
+            let next = (pos.0 + dir.0, pos.1 + dir.1);
+
+            match cell(next) {
+                None => break,
+                Some(b) if b == WALL_BLOCK as u8 => dir = (dir.1, -dir.0),
+                Some(_) => {
+                    if !steps.insert((pos, dir)) {
+                        break;
+                    }
+                    pos = next;
+                    visited.insert(pos);
+                }
+            }
+        }
+
+        visited.len() as u32
+    }
+
+    #[test]
+    fn generated_labs() {
+        for (seed, input) in generate::inputs(input, 30) {
+            // square, with a single guard
+            assert_eq!(input.lines().count(), 30, "seed {seed}");
+            assert!(input.lines().all(|line| line.len() == 30), "seed {seed}");
+            assert_eq!(input.matches(START_BLOCK).count(), 1, "seed {seed}");
+
+            assert_eq!(part1(&input), Some(walk(&input)), "seed {seed}");
+        }
+    }
+}
@@ -1,3 +1,15 @@
+#[cfg(test)]
+mod examples;
+mod generate;
+
+use std::iter;
+
+use aoc::{
+    anim::{Animation, Color, Frame},
+    bitgrid::BitGrid,
+    image::Image,
+};
+
 const EMPTY_BLOCK: char = '.';
 const START_BLOCK: char = '^';
 const WALL_BLOCK: char = '#';
@@ -66,34 +78,104 @@ fn step(
     (next_pos, next_dir)
 }
 
+/// `None` once the guard is out of the grid
+type State = Option<((isize, isize), (isize, isize))>;
+
+fn next_state(grid: &[char], size: (isize, isize), state: &State) -> State {
+    let (pos, dir) = (*state)?;
+    let (next_pos, next_dir) = step(grid, size, pos, dir);
+
+    is_inside(next_pos, size).then_some((next_pos, next_dir))
+}
+
+/// cells the guard walks on until it leaves the grid, or until it starts going in circles.
+/// `on_step` gets every state (`None` once out) along with the cells walked on so far, and stops
+/// the walk by returning `false`
 fn traverse(
     grid: &[char],
     size: (isize, isize),
     start_pos: (isize, isize),
-) -> (bool, Vec<(isize, isize)>) {
-    let mut pos = start_pos;
-    let mut dir = (-1, 0);
-    let mut visited = vec![(0, 0); grid.len()];
-    let mut is_loop = false;
-
-    while is_inside(pos, size) {
-        if visited[coords_2to1(pos, size)] == dir {
-            is_loop = true;
-            break;
+    mut on_step: impl FnMut(State, &BitGrid) -> bool,
+) -> BitGrid {
+    let start = Some((start_pos, (-1, 0)));
+    let next = |state: &State| next_state(grid, size, state);
+
+    let cycle = aoc::cycle::brent(start, next);
+    let mut visited = BitGrid::new(size.1 as usize, size.0 as usize);
+
+    for state in
+        iter::successors(Some(start), |state| Some(next(state))).take(cycle.prefix + cycle.length)
+    {
+        if let Some((pos, _)) = state {
+            visited.set(pos.1 as usize, pos.0 as usize, true);
         }
-        visited[coords_2to1(pos, size)] = dir;
 
-        (pos, dir) = step(grid, size, pos, dir);
+        if !on_step(state, &visited) {
+            break;
+        }
     }
 
-    (is_loop, visited)
+    visited
+}
+
+/// a loop is a repeated (position, direction): the same cell can be crossed in different directions
+fn is_loop(grid: &[char], size: (isize, isize), start_pos: (isize, isize)) -> bool {
+    let start = Some((start_pos, (-1, 0)));
+    let cycle = aoc::cycle::brent(start, |state| next_state(grid, size, state));
+
+    cycle.start.is_some()
+}
+
+/// the guard's walk, one frame per step, until it leaves the grid or goes around its loop once
+fn animate(input: &str, animation: &mut Animation) -> Option<()> {
+    let (grid, size, start_pos) = parse_input(input)?;
+
+    traverse(&grid, size, start_pos, |state, visited| {
+        let frame = Frame::from_fn(size.1 as usize, size.0 as usize, |x, y| {
+            let pos = (y as isize, x as isize);
+            let i = coords_2to1(pos, size);
+
+            match state {
+                Some((guard, dir)) if guard == pos => match dir {
+                    (-1, 0) => ('^', Color::Red),
+                    (0, 1) => ('>', Color::Red),
+                    (1, 0) => ('v', Color::Red),
+                    _ => ('<', Color::Red),
+                },
+                _ if grid[i] == WALL_BLOCK => (WALL_BLOCK, Color::Gray),
+                _ if visited.get(x, y) => ('X', Color::Yellow),
+                _ => (EMPTY_BLOCK, Color::Default),
+            }
+        });
+
+        animation.show(frame)
+    });
+
+    Some(())
+}
+
+fn visited_image(input: &str) -> Option<Image> {
+    let (grid, size, start) = parse_input(input)?;
+    let visited = traverse(&grid, size, start, |_, _| true);
+
+    let image = Image::from_fn(size.1 as usize, size.0 as usize, |x, y| {
+        let i = coords_2to1((y as isize, x as isize), size);
+
+        match grid[i] {
+            WALL_BLOCK => Color::Gray.rgb(),
+            _ if visited.get(x, y) => Color::Yellow.rgb(),
+            _ => Color::Default.rgb(),
+        }
+    });
+
+    Some(image.scaled(4))
 }
 
 fn part1(input: &str) -> Option<u32> {
     let (grid, size, start) = parse_input(input)?;
 
-    let (_, visited) = traverse(&grid, size, start);
-    let count = visited.iter().filter(|&d| *d != (0, 0)).count() as u32;
+    let visited = traverse(&grid, size, start, |_, _| true);
+    let count = visited.count_ones() as u32;
 
     Some(count)
 }
@@ -101,17 +183,17 @@ fn part1(input: &str) -> Option<u32> {
 fn part2(input: &str) -> Option<u32> {
     let (mut grid, size, start) = parse_input(input)?;
 
-    let (_, visited) = traverse(&grid, size, start);
+    let visited = traverse(&grid, size, start, |_, _| true);
 
     let start_1d = coords_2to1(start, size);
 
     let total = visited
-        .into_iter()
-        .enumerate()
-        .filter(|&(p, d)| p != start_1d && d != (0, 0))
-        .fold(0, |total, (pos_1d, _)| {
+        .ones()
+        .map(|(x, y)| coords_2to1((y as isize, x as isize), size))
+        .filter(|&pos_1d| pos_1d != start_1d)
+        .fold(0, |total, pos_1d| {
             grid[pos_1d] = WALL_BLOCK;
-            let (is_loop, _) = traverse(&grid, size, start);
+            let is_loop = is_loop(&grid, size, start);
             grid[pos_1d] = EMPTY_BLOCK;
 
             total + is_loop as u32
@@ -122,11 +204,24 @@ fn part2(input: &str) -> Option<u32> {
 
 fn main() {
     let input0 = include_str!("input0.txt");
-    let input1 = include_str!("input1.txt");
+    let input1 = aoc::generate::input_or(include_str!("input1.txt"), generate::input);
 
-    println!("{:?}", part1(input0));
-    println!("{:?}", part1(input1));
+    if let Some(dir) = aoc::image::dir_from_args() {
+        let image = visited_image(input1).expect("no guard");
+        image.save(dir.join("visited.png")).unwrap();
+        return;
+    }
+
+    if let Some(mut animation) = Animation::from_args() {
+        animate(input0, &mut animation);
+        return;
+    }
 
-    println!("{:?}", part2(input0));
-    println!("{:?}", part2(input1));
+    aoc::run::Day::new("2024/day6")
+        .input("input0", input0)
+        .input("input1", input1)
+        .parse("parse", parse_input)
+        .part(1, part1)
+        .part(2, part2)
+        .run();
 }
@@ -0,0 +1,572 @@
+//! frame by frame animations of grid simulations in the terminal.
+//!
+//! a day builds a [`Frame`] per step and hands it to [`Animation::show`], which only redraws the
+//! cells that changed since the previous frame. in the terminal it's controlled with the keyboard:
+//! - `space`: play / pause
+//! - `n` or `.`: next frame, while paused
+//! - `+` / `-`: twice faster / slower
+//! - `q`: stop drawing, the simulation keeps going to get the answer
+//!
+//! a day opts in from its `main` when started with `--animate`.
+//! other flags: `--fps N` (default 30) and `--paused` to start paused.
+//! with `--frames DIR` instead, every frame is saved as a numbered PNG in `DIR`,
+//! with `--scale N` pixels per cell (default 4).
+//! the headless mode keeps the frames instead of drawing them, for tests
+
+use std::{
+    env,
+    fmt::{self, Write as _},
+    io::{self, IsTerminal, Read, Write as _},
+    path::PathBuf,
+    process::{Command, Stdio},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::image::{Image, Rgb, Sequence};
+
+/// the 8 basic terminal colours, plus a gray that's readable on both dark and light themes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Color {
+    #[default]
+    Default,
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Gray,
+}
+
+impl Color {
+    fn code(self) -> u8 {
+        match self {
+            Color::Default => 39,
+            Color::Black => 30,
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+            Color::White => 37,
+            Color::Gray => 90,
+        }
+    }
+
+    /// in images, where `Default` is for the cells that don't stand out
+    pub fn rgb(self) -> Rgb {
+        match self {
+            Color::Default => [48, 48, 48],
+            Color::Black => [0, 0, 0],
+            Color::Red => [205, 49, 49],
+            Color::Green => [13, 188, 121],
+            Color::Yellow => [229, 229, 16],
+            Color::Blue => [36, 114, 200],
+            Color::Magenta => [188, 63, 188],
+            Color::Cyan => [17, 168, 205],
+            Color::White => [229, 229, 229],
+            Color::Gray => [118, 118, 118],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub ch: char,
+    pub color: Color,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Cell {
+            ch: ' ',
+            color: Color::Default,
+        }
+    }
+}
+
+/// a grid of coloured characters, `(0, 0)` being the top left
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+}
+
+impl Frame {
+    /// filled with spaces
+    pub fn new(width: usize, height: usize) -> Self {
+        Frame {
+            width,
+            height,
+            cells: vec![Cell::default(); width * height],
+        }
+    }
+
+    /// `f` gives the character of every `(x, y)`, and its colour, usually picked by what kind of
+    /// cell it is (wall, visited, ...)
+    pub fn from_fn(
+        width: usize,
+        height: usize,
+        mut f: impl FnMut(usize, usize) -> (char, Color),
+    ) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let (ch, color) = f(x, y);
+                Cell { ch, color }
+            })
+            .collect();
+
+        Frame {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<Cell> {
+        (x < self.width && y < self.height).then(|| self.cells[x + y * self.width])
+    }
+
+    /// does nothing outside of the frame
+    pub fn set(&mut self, x: usize, y: usize, ch: char, color: Color) {
+        if x < self.width && y < self.height {
+            self.cells[x + y * self.width] = Cell { ch, color };
+        }
+    }
+
+    /// the cells that differ from `previous`, all of them if it's missing or of another size
+    fn changes<'a>(
+        &'a self,
+        previous: Option<&'a Frame>,
+    ) -> impl Iterator<Item = (usize, usize, Cell)> + 'a {
+        let previous = previous.filter(|p| (p.width, p.height) == (self.width, self.height));
+
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(move |&(i, cell)| previous.is_none_or(|p| p.cells[i] != *cell))
+            .map(|(i, &cell)| (i % self.width, i / self.width, cell))
+    }
+}
+
+/// only the characters, one line per row
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.cells.chunks(self.width.max(1)).enumerate() {
+            if y > 0 {
+                f.write_char('\n')?;
+            }
+            for cell in row {
+                f.write_char(cell.ch)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// the terminal in raw mode, so that keys are read as soon as they're pressed
+struct Terminal {
+    /// dropped after [`Terminal::drop`] has cleaned the screen up
+    _raw: Option<RawMode>,
+    /// `None` if stdin is not a terminal, then there are no controls
+    keys: Option<Receiver<u8>>,
+}
+
+/// puts the terminal back the way it was when dropped, even while unwinding from a panic
+struct RawMode {
+    /// what `stty -g` said before
+    saved: String,
+}
+
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_owned())
+}
+
+impl RawMode {
+    /// `None` if stdin is not a terminal or `stty` failed
+    fn enter() -> Option<Self> {
+        if !io::stdin().is_terminal() {
+            return None;
+        }
+        // before switching, so that even a half done switch is undone
+        let guard = RawMode {
+            saved: stty(&["-g"])?,
+        };
+        stty(&["-icanon", "-echo", "min", "1"])?;
+
+        Some(guard)
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        stty(&[&self.saved]);
+    }
+}
+
+impl Terminal {
+    fn new() -> Self {
+        let raw = RawMode::enter();
+
+        let keys = raw.as_ref().map(|_| {
+            let (sender, receiver) = mpsc::channel();
+            thread::spawn(move || {
+                for byte in io::stdin().lock().bytes() {
+                    let Ok(byte) = byte else { break };
+                    if sender.send(byte).is_err() {
+                        break;
+                    }
+                }
+            });
+            receiver
+        });
+
+        // clear the screen and hide the cursor
+        print!("\x1b[2J\x1b[?25l");
+
+        Terminal { _raw: raw, keys }
+    }
+
+    /// redraws what changed since `previous`, then the status line under the frame
+    fn draw(&self, frame: &Frame, previous: Option<&Frame>, status: &str) -> io::Result<()> {
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(render(frame, previous, status).as_bytes())?;
+        stdout.flush()
+    }
+}
+
+/// what [`Terminal::draw`] writes: only the cells that changed, moving the cursor when they're
+/// not next to each other and switching colours when they differ
+fn render(frame: &Frame, previous: Option<&Frame>, status: &str) -> String {
+    let mut out = String::new();
+    let mut cursor = None;
+    let mut color = None;
+
+    if previous.is_none_or(|p| (p.width, p.height) != (frame.width, frame.height)) {
+        out.push_str("\x1b[2J");
+    }
+
+    for (x, y, cell) in frame.changes(previous) {
+        if cursor != Some((x, y)) {
+            let _ = write!(out, "\x1b[{};{}H", y + 1, x + 1);
+        }
+        if color != Some(cell.color) {
+            let _ = write!(out, "\x1b[{}m", cell.color.code());
+            color = Some(cell.color);
+        }
+        out.push(cell.ch);
+        cursor = Some((x + 1, y));
+    }
+
+    let _ = write!(out, "\x1b[0m\x1b[{};1H\x1b[2K{status}", frame.height + 2);
+    out
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        // colours back to normal, cursor visible again, and below everything that was drawn
+        println!("\x1b[0m\x1b[?25h");
+        let _ = io::stdout().flush();
+    }
+}
+
+enum Output {
+    Terminal(Terminal),
+    Headless(Vec<Frame>),
+    Files(Sequence),
+}
+
+pub struct Animation {
+    output: Output,
+    fps: f64,
+    paused: bool,
+    stopped: bool,
+    shown: usize,
+    previous: Option<Frame>,
+}
+
+impl Animation {
+    /// takes over the terminal until dropped
+    pub fn terminal(fps: f64) -> Self {
+        Animation {
+            output: Output::Terminal(Terminal::new()),
+            fps,
+            paused: false,
+            stopped: false,
+            shown: 0,
+            previous: None,
+        }
+    }
+
+    /// keeps every frame, see [`Animation::frames`]
+    pub fn headless() -> Self {
+        Animation {
+            output: Output::Headless(Vec::new()),
+            fps: f64::INFINITY,
+            paused: false,
+            stopped: false,
+            shown: 0,
+            previous: None,
+        }
+    }
+
+    /// saves the frames as numbered PNGs in `dir`, with `scale` pixels per cell
+    pub fn files(dir: impl Into<PathBuf>, scale: usize) -> io::Result<Self> {
+        Ok(Animation {
+            output: Output::Files(Sequence::new(dir, scale)?),
+            ..Animation::headless()
+        })
+    }
+
+    /// `None` unless the program was started with `--animate` or `--frames`
+    pub fn from_args() -> Option<Self> {
+        let mut enabled = false;
+        let mut fps = 30.;
+        let mut paused = false;
+        let mut frames = None;
+        let mut scale = 4;
+        let mut args = env::args().skip(1);
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--animate" => enabled = true,
+                "--fps" => fps = args.next().and_then(|n| n.parse().ok()).expect("--fps N"),
+                "--paused" => paused = true,
+                "--frames" => frames = Some(PathBuf::from(args.next().expect("--frames DIR"))),
+                "--scale" => scale = args.next().and_then(|n| n.parse().ok()).expect("--scale N"),
+                _ => (),
+            }
+        }
+
+        if let Some(dir) = frames {
+            return Some(Animation::files(dir, scale).expect("couldn't create the frames folder"));
+        }
+
+        enabled.then(|| Animation {
+            paused,
+            ..Animation::terminal(fps)
+        })
+    }
+
+    /// the frames shown so far in headless mode, empty otherwise
+    pub fn frames(&self) -> &[Frame] {
+        match &self.output {
+            Output::Headless(frames) => frames,
+            Output::Terminal(_) | Output::Files(_) => &[],
+        }
+    }
+
+    /// the last frame shown, whatever the mode
+    pub fn last_frame(&self) -> Option<&Frame> {
+        match &self.output {
+            Output::Headless(frames) => frames.last(),
+            Output::Terminal(_) | Output::Files(_) => self.previous.as_ref(),
+        }
+    }
+
+    /// draws `frame` and waits for the next one to be due.
+    /// `false` once stopped with `q`, nothing is drawn from then on
+    pub fn show(&mut self, frame: Frame) -> bool {
+        if self.stopped {
+            return false;
+        }
+        self.shown += 1;
+
+        let terminal = match &mut self.output {
+            Output::Headless(frames) => {
+                frames.push(frame);
+                return true;
+            }
+            Output::Files(sequence) => {
+                if let Err(err) = sequence.save(&Image::from_frame(&frame)) {
+                    eprintln!("couldn't save frame {}: {err}", self.shown);
+                    self.stopped = true;
+                }
+                self.previous = Some(frame);
+                return !self.stopped;
+            }
+            Output::Terminal(terminal) => terminal,
+        };
+
+        let start = Instant::now();
+        let mut previous = self.previous.as_ref();
+
+        loop {
+            // again every time around, since `+` and `-` change the speed
+            let due = start + Duration::from_secs_f64(1. / self.fps);
+            let status = format!(
+                "frame {}  {:.1} fps{}  [space] play/pause  [n] step  [+/-] speed  [q] quit",
+                self.shown,
+                self.fps,
+                if self.paused { "  PAUSED" } else { "" }
+            );
+            // a broken terminal is not worth stopping the simulation for
+            let _ = terminal.draw(&frame, previous, &status);
+            previous = Some(&frame);
+
+            let Some(keys) = &terminal.keys else {
+                thread::sleep(due.saturating_duration_since(Instant::now()));
+                break;
+            };
+
+            let key = if self.paused {
+                keys.recv().map_err(|_| RecvTimeoutError::Disconnected)
+            } else {
+                keys.recv_timeout(due.saturating_duration_since(Instant::now()))
+            };
+
+            match key {
+                Ok(b' ') => self.paused = !self.paused,
+                Ok(b'n' | b'.') if self.paused => break,
+                Ok(b'+') => self.fps *= 2.,
+                Ok(b'-') => self.fps /= 2.,
+                Ok(b'q') => {
+                    self.stopped = true;
+                    break;
+                }
+                Ok(_) => (),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => {
+                    terminal.keys = None;
+                    self.paused = false;
+                }
+            }
+        }
+
+        self.previous = Some(frame);
+        !self.stopped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, process};
+
+    use super::*;
+
+    fn frame(rows: &[&str]) -> Frame {
+        let rows = rows
+            .iter()
+            .map(|row| row.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        Frame::from_fn(rows[0].len(), rows.len(), |x, y| {
+            (rows[y][x], Color::Default)
+        })
+    }
+
+    fn cell(ch: char, color: Color) -> Cell {
+        Cell { ch, color }
+    }
+
+    #[test]
+    fn frames_of_characters() {
+        let mut frame = frame(&["ab", "cd"]);
+        assert_eq!(frame.to_string(), "ab\ncd");
+        assert_eq!(frame.get(1, 0), Some(cell('b', Color::Default)));
+        assert_eq!(frame.get(2, 0), None);
+
+        frame.set(0, 1, '#', Color::Red);
+        frame.set(2, 1, '#', Color::Red);
+        assert_eq!(frame.to_string(), "ab\n#d");
+        assert_eq!(frame.get(0, 1), Some(cell('#', Color::Red)));
+    }
+
+    #[test]
+    fn only_the_cells_that_changed() {
+        let before = frame(&["...", "..."]);
+        let mut after = before.clone();
+        after.set(2, 0, '#', Color::Red);
+        after.set(0, 1, 'X', Color::Yellow);
+
+        assert_eq!(
+            after.changes(Some(&before)).collect::<Vec<_>>(),
+            [
+                (2, 0, cell('#', Color::Red)),
+                (0, 1, cell('X', Color::Yellow))
+            ]
+        );
+        assert_eq!(before.changes(Some(&before)).count(), 0);
+
+        // everything, without a previous frame of the same size
+        assert_eq!(after.changes(None).count(), 6);
+        assert_eq!(after.changes(Some(&frame(&["..", ".."]))).count(), 6);
+    }
+
+    #[test]
+    fn redrawn_from_what_changed() {
+        let before = frame(&["...", "..."]);
+        let mut after = before.clone();
+        after.set(1, 0, '#', Color::Red);
+        after.set(2, 0, '#', Color::Red);
+        after.set(0, 1, 'X', Color::Yellow);
+
+        let status = "\x1b[0m\x1b[4;1H\x1b[2Kstatus";
+        assert_eq!(render(&before, Some(&before), "status"), status);
+        assert_eq!(
+            render(&after, Some(&before), "status"),
+            format!("\x1b[1;2H\x1b[31m##\x1b[2;1H\x1b[33mX{status}")
+        );
+
+        // from a clear screen otherwise
+        let first = render(&before, None, "status");
+        assert!(first.starts_with("\x1b[2J\x1b[1;1H\x1b[39m...\x1b[2;1H..."));
+        assert!(first.ends_with(status));
+    }
+
+    #[test]
+    fn headless_keeps_every_frame() {
+        let mut animation = Animation::headless();
+        assert_eq!(animation.last_frame(), None);
+
+        for row in ["a", "b", "c"] {
+            assert!(animation.show(frame(&[row])));
+        }
+
+        let frames = animation.frames().iter().map(ToString::to_string);
+        assert_eq!(frames.collect::<Vec<_>>(), ["a", "b", "c"]);
+        assert_eq!(animation.last_frame(), Some(&frame(&["c"])));
+    }
+
+    #[test]
+    fn files_are_numbered() {
+        let dir = env::temp_dir().join(format!("aoc-anim-{}", process::id()));
+        let mut animation = Animation::files(&dir, 2).unwrap();
+
+        assert!(animation.show(frame(&["ab"])));
+        assert!(animation.show(frame(&["cd"])));
+
+        assert!(dir.join("00000.png").is_file() && dir.join("00001.png").is_file());
+        assert!(!dir.join("00002.png").exists());
+        assert!(animation.frames().is_empty());
+        assert_eq!(animation.last_frame(), Some(&frame(&["cd"])));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,14 @@
+//! the flags a day reads itself, besides the ones of the other modules
+
+use std::env;
+
+/// the value given after `flag`, `None` without `flag`. panics if `flag` is the last argument
+pub fn after(flag: &str) -> Option<String> {
+    let mut args = env::args().skip(1);
+    args.find(|arg| arg == flag)?;
+
+    Some(
+        args.next()
+            .unwrap_or_else(|| panic!("missing value for {flag}")),
+    )
+}
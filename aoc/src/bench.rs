@@ -0,0 +1,317 @@
+//! repeated timings of a day's functions, to get numbers that don't depend on a single lucky run.
+//!
+//! every day gets it through [`run`](crate::run), and the harness only kicks in when the program
+//! is started with `--bench`. other flags:
+//! - `--runs N`: timed runs per function, at least 1 (default 100)
+//! - `--warmup N`: untimed runs before those (default 10, within a fifth of `--max-time`)
+//! - `--max-time SECS`: stop early once a function has been timed for this long (default 5)
+//! - `--baseline FILE`: compare with the results saved in `FILE`
+//! - `--save`: write the results in the baseline file, replacing older ones with the same name.
+//!   needs `--baseline`, and a baseline that's there but can't be read is an error rather than
+//!   something to overwrite
+//! - `--threshold PCT`: how much slower than the baseline counts as a regression (default 10)
+
+use std::{
+    env, fmt, fs,
+    hint::black_box,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    process,
+    time::{Duration, Instant},
+};
+
+use crate::json::{self, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// `None` if there are no samples
+    pub fn from_samples(mut samples: Vec<Duration>) -> Option<Self> {
+        samples.sort_unstable();
+
+        let runs = samples.len();
+        let p95 = (runs * 95).div_ceil(100).max(1) - 1;
+
+        Some(Stats {
+            runs,
+            min: *samples.first()?,
+            median: samples[runs / 2],
+            p95: samples[p95],
+        })
+    }
+
+    fn to_json(self) -> Value {
+        Value::Object(vec![
+            ("runs".into(), (self.runs as f64).into()),
+            ("min_ns".into(), (self.min.as_nanos() as f64).into()),
+            ("median_ns".into(), (self.median.as_nanos() as f64).into()),
+            ("p95_ns".into(), (self.p95.as_nanos() as f64).into()),
+        ])
+    }
+
+    fn from_json(value: &Value) -> Option<Self> {
+        let duration = |key| Some(Duration::from_nanos(value.get(key)?.as_f64()? as u64));
+
+        Some(Stats {
+            runs: value.get("runs")?.as_f64()? as usize,
+            min: duration("min_ns")?,
+            median: duration("median_ns")?,
+            p95: duration("p95_ns")?,
+        })
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:>10.2?}  median {:>10.2?}  p95 {:>10.2?}  ({} runs)",
+            self.min, self.median, self.p95, self.runs
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Options {
+    runs: usize,
+    warmup: usize,
+    max_time: Duration,
+    baseline: Option<PathBuf>,
+    save: bool,
+    threshold: f64,
+}
+
+impl Options {
+    /// `None` unless `--bench` is among `args`
+    fn parse(args: impl IntoIterator<Item = String>) -> Option<Self> {
+        let mut options = Options {
+            runs: 100,
+            warmup: 10,
+            max_time: Duration::from_secs(5),
+            baseline: None,
+            save: false,
+            threshold: 10.,
+        };
+        let mut enabled = false;
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .unwrap_or_else(|| panic!("missing value for {arg}"))
+            };
+
+            match arg.as_str() {
+                "--bench" => enabled = true,
+                "--runs" => {
+                    options.runs = value()
+                        .parse()
+                        .ok()
+                        .filter(|&runs| runs > 0)
+                        .expect("--runs N, with at least 1 run")
+                }
+                "--warmup" => options.warmup = value().parse().expect("--warmup N"),
+                "--max-time" => {
+                    options.max_time =
+                        Duration::from_secs_f64(value().parse().expect("--max-time SECS"))
+                }
+                "--baseline" => options.baseline = Some(PathBuf::from(value())),
+                "--save" => options.save = true,
+                "--threshold" => options.threshold = value().parse().expect("--threshold PCT"),
+                _ => (),
+            }
+        }
+
+        enabled.then_some(options)
+    }
+}
+
+/// the saved results, nothing if there's no file yet
+fn load_baseline(path: &Path) -> Result<Value, String> {
+    let s = match fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Value::Object(Vec::new())),
+        Err(e) => {
+            return Err(format!(
+                "couldn't read the baseline {}: {e}",
+                path.display()
+            ))
+        }
+    };
+
+    match json::parse(&s) {
+        Some(baseline @ Value::Object(_)) => Ok(baseline),
+        _ => Err(format!("{} isn't a baseline", path.display())),
+    }
+}
+
+/// timings of every function of a day, named `name/label` (e.g. `2015/day6/part1`)
+pub struct Bench {
+    name: String,
+    options: Options,
+    results: Vec<(String, Stats)>,
+}
+
+impl Bench {
+    /// `None` unless the program was started with `--bench`
+    pub fn from_args(name: &str) -> Option<Self> {
+        let options = Options::parse(env::args().skip(1))?;
+
+        Some(Bench {
+            name: name.to_owned(),
+            options,
+            results: Vec::new(),
+        })
+    }
+
+    /// times `workload`, after a few runs to warm up caches and the branch predictor
+    pub fn run<T>(&mut self, label: &str, mut workload: impl FnMut() -> T) -> &mut Self {
+        let started = Instant::now();
+        for _ in 0..self.options.warmup {
+            if started.elapsed() >= self.options.max_time / 5 {
+                break;
+            }
+            black_box(workload());
+        }
+
+        let started = Instant::now();
+        let mut samples = Vec::with_capacity(self.options.runs);
+
+        while samples.len() < self.options.runs
+            && (samples.is_empty() || started.elapsed() < self.options.max_time)
+        {
+            let before = Instant::now();
+            black_box(workload());
+            samples.push(before.elapsed());
+        }
+
+        let stats = Stats::from_samples(samples).unwrap();
+        println!("{}/{label}: {stats}", self.name);
+        self.results.push((format!("{}/{label}", self.name), stats));
+
+        self
+    }
+
+    /// compares with the baseline, and saves the results in it if asked to. exits with an error
+    /// if that can't be done
+    pub fn finish(&self) {
+        if let Err(e) = self.compare_and_save() {
+            eprintln!("{}: {e}", self.name);
+            process::exit(1);
+        }
+    }
+
+    fn compare_and_save(&self) -> Result<(), String> {
+        let Some(path) = &self.options.baseline else {
+            return match self.options.save {
+                true => Err("--save needs --baseline FILE".to_owned()),
+                false => Ok(()),
+            };
+        };
+
+        let mut baseline = load_baseline(path)?;
+
+        for (key, stats) in &self.results {
+            let Some(old) = baseline.get(key).and_then(Stats::from_json) else {
+                println!("{key}: not in the baseline");
+                continue;
+            };
+
+            let change = (stats.median.as_secs_f64() / old.median.as_secs_f64() - 1.) * 100.;
+            let verdict = if change > self.options.threshold {
+                "REGRESSION"
+            } else if change < -self.options.threshold {
+                "improvement"
+            } else {
+                "unchanged"
+            };
+
+            println!(
+                "{key}: median {:.2?} -> {:.2?} ({change:+.1}%) {verdict}",
+                old.median, stats.median
+            );
+        }
+
+        if self.options.save {
+            for (key, stats) in &self.results {
+                baseline.set(key, stats.to_json());
+            }
+
+            fs::write(path, format!("{baseline}\n"))
+                .map_err(|e| format!("couldn't write the baseline {}: {e}", path.display()))?;
+            println!("saved in {}", path.display());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bench(baseline: Option<PathBuf>, save: bool) -> Bench {
+        let args = ["--bench", "--runs", "3", "--warmup", "0"].map(String::from);
+        let options = Options {
+            baseline,
+            save,
+            ..Options::parse(args).unwrap()
+        };
+
+        let mut bench = Bench {
+            name: "test".to_owned(),
+            options,
+            results: Vec::new(),
+        };
+        bench.run("sum", || (0..100).sum::<u32>());
+        bench
+    }
+
+    fn temp(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("aoc-bench-{}-{name}.json", process::id()))
+    }
+
+    #[test]
+    fn saving_needs_a_baseline() {
+        assert!(bench(None, true).compare_and_save().is_err());
+        assert!(bench(None, false).compare_and_save().is_ok());
+    }
+
+    #[test]
+    fn baselines_are_saved_and_read_back() {
+        let path = temp("saved");
+        let _ = fs::remove_file(&path);
+
+        let bench = bench(Some(path.clone()), true);
+        bench.compare_and_save().unwrap();
+
+        let baseline = load_baseline(&path).unwrap();
+        let saved = baseline.get("test/sum").and_then(Stats::from_json);
+        assert_eq!(saved, Some(bench.results[0].1));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn broken_baselines_are_not_overwritten() {
+        let path = temp("broken");
+        fs::write(&path, "{\"test/sum\": ").unwrap();
+
+        assert!(bench(Some(path.clone()), true).compare_and_save().is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"test/sum\": ");
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "--runs N, with at least 1 run")]
+    fn no_runs_is_an_error() {
+        Options::parse(["--bench", "--runs", "0"].map(String::from));
+    }
+}
@@ -0,0 +1,276 @@
+//! a grid of booleans packed 64 to a word. each row starts on a word of its own, so filling,
+//! toggling or counting along a row is done a word at a time, and the bits past the width are
+//! always 0
+
+use std::ops::{BitAndAssign, BitOrAssign, BitXorAssign, Range};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    /// words per row
+    stride: usize,
+    words: Vec<u64>,
+}
+
+/// the words a range of bits is in, with the mask of its bits in each of them
+fn masks(xs: Range<usize>) -> impl Iterator<Item = (usize, u64)> {
+    let words = match xs.is_empty() {
+        true => 0..0,
+        false => xs.start / 64..xs.end.div_ceil(64),
+    };
+
+    words.map(move |w| {
+        let lo = xs.start.max(w * 64) - w * 64;
+        let hi = xs.end.min(w * 64 + 64) - w * 64;
+        let ones = match hi - lo {
+            64 => !0,
+            len => (1 << len) - 1,
+        };
+
+        (w, ones << lo)
+    })
+}
+
+impl BitGrid {
+    /// every cell unset
+    pub fn new(width: usize, height: usize) -> Self {
+        let stride = width.div_ceil(64);
+
+        BitGrid {
+            width,
+            height,
+            stride,
+            words: vec![0; stride * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// the index of the word with a cell in it, and the bit of the cell in that word
+    fn bit(&self, x: usize, y: usize) -> (usize, u64) {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is out of a {}x{} grid",
+            self.width,
+            self.height
+        );
+
+        (y * self.stride + x / 64, 1 << (x % 64))
+    }
+
+    /// the words of a row a range of it is in, with their masks
+    fn row(&self, y: usize, xs: Range<usize>) -> impl Iterator<Item = (usize, u64)> {
+        assert!(
+            y < self.height && xs.end <= self.width,
+            "row {y}, columns {xs:?} are out of a {}x{} grid",
+            self.width,
+            self.height
+        );
+        let start = y * self.stride;
+
+        masks(xs).map(move |(w, mask)| (start + w, mask))
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        let (w, bit) = self.bit(x, y);
+        self.words[w] & bit != 0
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: bool) {
+        let (w, bit) = self.bit(x, y);
+
+        match value {
+            true => self.words[w] |= bit,
+            false => self.words[w] &= !bit,
+        }
+    }
+
+    pub fn toggle(&mut self, x: usize, y: usize) {
+        let (w, bit) = self.bit(x, y);
+        self.words[w] ^= bit;
+    }
+
+    /// sets the cells in columns `xs` of row `y` to `value`
+    pub fn fill_row(&mut self, y: usize, xs: Range<usize>, value: bool) {
+        for (w, mask) in self.row(y, xs) {
+            match value {
+                true => self.words[w] |= mask,
+                false => self.words[w] &= !mask,
+            }
+        }
+    }
+
+    pub fn toggle_row(&mut self, y: usize, xs: Range<usize>) {
+        for (w, mask) in self.row(y, xs) {
+            self.words[w] ^= mask;
+        }
+    }
+
+    /// how many cells are set in columns `xs` of row `y`
+    pub fn count_row(&self, y: usize, xs: Range<usize>) -> usize {
+        self.row(y, xs)
+            .map(|(w, mask)| (self.words[w] & mask).count_ones() as usize)
+            .sum()
+    }
+
+    /// how many cells are set
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// the `(x, y)` of every cell that's set, row by row
+    pub fn ones(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, &word)| {
+            let (y, x0) = (i / self.stride, i % self.stride * 64);
+
+            // the lowest bit left is the next cell
+            std::iter::successors(Some(word).filter(|&w| w != 0), |&w| {
+                Some(w & (w - 1)).filter(|&w| w != 0)
+            })
+            .map(move |w| (x0 + w.trailing_zeros() as usize, y))
+        })
+    }
+
+    fn zip_with(&mut self, other: &BitGrid, f: impl Fn(&mut u64, u64)) {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "grids of different sizes"
+        );
+
+        for (word, &other) in self.words.iter_mut().zip(&other.words) {
+            f(word, other);
+        }
+    }
+}
+
+impl BitAndAssign<&BitGrid> for BitGrid {
+    fn bitand_assign(&mut self, other: &BitGrid) {
+        self.zip_with(other, |w, o| *w &= o);
+    }
+}
+
+impl BitOrAssign<&BitGrid> for BitGrid {
+    fn bitor_assign(&mut self, other: &BitGrid) {
+        self.zip_with(other, |w, o| *w |= o);
+    }
+}
+
+impl BitXorAssign<&BitGrid> for BitGrid {
+    fn bitxor_assign(&mut self, other: &BitGrid) {
+        self.zip_with(other, |w, o| *w ^= o);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 130 wide: two full words and a bit of a third on every row
+    fn grid(cells: &[(usize, usize)]) -> BitGrid {
+        let mut grid = BitGrid::new(130, 3);
+        for &(x, y) in cells {
+            grid.set(x, y, true);
+        }
+        grid
+    }
+
+    #[test]
+    fn cells_are_set_got_and_toggled() {
+        let mut grid = BitGrid::new(130, 3);
+        assert_eq!((grid.width(), grid.height()), (130, 3));
+
+        grid.set(5, 1, true);
+        grid.toggle(64, 2);
+        assert!(grid.get(5, 1) && grid.get(64, 2));
+        assert!(!grid.get(5, 0) && !grid.get(63, 2) && !grid.get(65, 2));
+
+        grid.set(5, 1, false);
+        grid.toggle(64, 2);
+        grid.set(0, 0, false);
+        assert_eq!(grid, BitGrid::new(130, 3));
+    }
+
+    #[test]
+    fn rows_end_at_the_width() {
+        let mut grid = BitGrid::new(130, 3);
+
+        // the last cell of a row and the first of the next one are in different words
+        grid.set(129, 0, true);
+        assert!(!grid.get(0, 1));
+        assert_eq!(grid.ones().collect::<Vec<_>>(), [(129, 0)]);
+
+        // a whole row leaves the bits past the width alone, and the rows around it too
+        grid.toggle_row(1, 0..130);
+        assert_eq!(grid.count_row(1, 0..130), 130);
+        assert_eq!(grid.count_ones(), 131);
+        grid.toggle_row(1, 0..130);
+        assert_eq!(grid.count_ones(), 1);
+    }
+
+    #[test]
+    #[should_panic = "out of a 130x3 grid"]
+    fn cells_past_the_width_are_out() {
+        BitGrid::new(130, 3).get(130, 0);
+    }
+
+    #[test]
+    fn ranges_of_a_row() {
+        let mut grid = BitGrid::new(130, 3);
+
+        // within a word, across the words, ending on a word, and empty
+        grid.fill_row(0, 3..10, true);
+        grid.fill_row(0, 60..70, true);
+        grid.fill_row(0, 120..128, true);
+        grid.fill_row(0, 50..50, true);
+        assert_eq!(grid.count_row(0, 0..130), 7 + 10 + 8);
+        assert_eq!(grid.count_row(0, 62..125), 8 + 5);
+        assert_eq!(grid.count_row(0, 10..60), 0);
+
+        grid.fill_row(0, 5..65, false);
+        grid.toggle_row(0, 0..8);
+        assert_eq!(grid.count_row(0, 0..130), 6 + 5 + 8);
+        assert_eq!(
+            (0..130).filter(|&x| grid.get(x, 0)).collect::<Vec<_>>(),
+            [0, 1, 2, 5, 6, 7]
+                .into_iter()
+                .chain(65..70)
+                .chain(120..128)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(grid.count_ones(), grid.count_row(0, 0..130));
+    }
+
+    #[test]
+    fn grids_combine_cell_by_cell() {
+        let a = grid(&[(0, 0), (64, 1), (129, 2)]);
+        let b = grid(&[(64, 1), (1, 0)]);
+
+        let mut and = a.clone();
+        and &= &b;
+        assert_eq!(and, grid(&[(64, 1)]));
+
+        let mut or = a.clone();
+        or |= &b;
+        assert_eq!(or, grid(&[(0, 0), (1, 0), (64, 1), (129, 2)]));
+
+        let mut xor = a.clone();
+        xor ^= &b;
+        assert_eq!(xor, grid(&[(0, 0), (1, 0), (129, 2)]));
+        assert_eq!(xor.ones().collect::<Vec<_>>(), [(0, 0), (1, 0), (129, 2)]);
+    }
+
+    #[test]
+    #[should_panic = "grids of different sizes"]
+    fn grids_of_different_sizes_dont_combine() {
+        let mut grid = BitGrid::new(130, 3);
+        grid |= &BitGrid::new(129, 3);
+    }
+}
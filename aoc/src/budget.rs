@@ -0,0 +1,242 @@
+//! a time budget for every part of a day, so a brute force that got slow doesn't go unnoticed.
+//!
+//! like the benchmarks, every day gets it through [`run`](crate::run), and the watchdog only kicks
+//! in when the program is started with `--budget SECS`. every part then runs in a process of its
+//! own, the same program started again with `--budget-part LABEL`, and one that's still going
+//! once the budget is spent is killed and reported as timed out, so it doesn't slow down the
+//! parts after it. with `--generate`, give a `--seed` too, or every part gets an input of its own.
+//! `run_year` runs every day of a year this way
+
+use std::{
+    env, fmt,
+    io::Read,
+    process::{self, Command, Stdio},
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// how a part went
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    Done(Duration),
+    TimedOut(Duration),
+    Panicked(Duration),
+}
+
+/// times are in seconds with a fixed format, so `run_year` can read them back
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Done(elapsed) => write!(f, "in {:.3}s", elapsed.as_secs_f64()),
+            Outcome::TimedOut(budget) => write!(f, "timed out after {}s", budget.as_secs_f64()),
+            Outcome::Panicked(elapsed) => {
+                write!(f, "panicked after {:.3}s", elapsed.as_secs_f64())
+            }
+        }
+    }
+}
+
+impl Outcome {
+    pub fn elapsed(&self) -> Duration {
+        match *self {
+            Outcome::Done(elapsed) | Outcome::TimedOut(elapsed) | Outcome::Panicked(elapsed) => {
+                elapsed
+            }
+        }
+    }
+
+    /// the name of the part and its outcome, from a line printed by [`Budget::run`]
+    pub fn from_line(line: &str) -> Option<(&str, Outcome)> {
+        let (name, rest) = line.split_once(": ")?;
+        let secs = |s: &str| {
+            let secs = s.strip_suffix('s')?.parse().ok()?;
+            Duration::try_from_secs_f64(secs).ok()
+        };
+
+        let outcome = if let Some(budget) = rest.strip_prefix("timed out after ") {
+            Outcome::TimedOut(secs(budget)?)
+        } else if let Some(elapsed) = rest.strip_prefix("panicked after ") {
+            Outcome::Panicked(secs(elapsed)?)
+        } else {
+            Outcome::Done(secs(rest.rsplit_once(" in ")?.1)?)
+        };
+
+        Some((name, outcome))
+    }
+}
+
+/// the parts of a day, named `name/label` like in the benchmarks
+pub struct Budget {
+    name: String,
+    budget: Duration,
+    failed: bool,
+    /// the part this process was started to run, by the one keeping the time
+    only: Option<String>,
+}
+
+impl Budget {
+    /// `None` unless the program was started with `--budget SECS`
+    pub fn from_args(name: &str) -> Option<Self> {
+        let mut args = env::args().skip_while(|arg| arg != "--budget");
+        args.next()?;
+        let budget = args
+            .next()
+            .and_then(|secs| secs.parse().ok())
+            .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+            .expect("--budget SECS");
+
+        let mut args = env::args().skip_while(|arg| arg != "--budget-part").skip(1);
+
+        Some(Budget {
+            name: name.to_owned(),
+            budget,
+            failed: false,
+            only: args.next(),
+        })
+    }
+
+    /// runs `part`, and prints its answer and how long it took, if it was within the budget
+    pub fn run<T: fmt::Debug>(&mut self, label: &str, part: impl FnOnce() -> T) -> &mut Self {
+        if let Some(only) = &self.only {
+            // for the process keeping the time: the seconds, then the answer
+            if label == only {
+                let started = Instant::now();
+                let answer = part();
+                println!("{} {answer:?}", started.elapsed().as_secs_f64());
+            }
+            return self;
+        }
+
+        let outcome = self.watch(label);
+        let (answer, outcome) = match outcome {
+            Ok((answer, elapsed)) => (answer + " ", Outcome::Done(elapsed)),
+            Err(outcome) => (String::new(), outcome),
+        };
+
+        self.failed |= !matches!(outcome, Outcome::Done(_));
+        println!("{}/{label}: {answer}{outcome}", self.name);
+
+        self
+    }
+
+    /// runs the part in a process of its own, and kills it once the budget is spent
+    fn watch(&self, label: &str) -> Result<(String, Duration), Outcome> {
+        let started = Instant::now();
+        let mut child = env::current_exe()
+            .and_then(|exe| {
+                Command::new(exe)
+                    .args(env::args().skip(1))
+                    .args(["--budget-part", label])
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::piped())
+                    .spawn()
+            })
+            .expect("couldn't start a part in a process of its own");
+
+        // the output only ends once the process does
+        let mut stdout = child.stdout.take().unwrap();
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let mut output = String::new();
+            let _ = stdout.read_to_string(&mut output);
+            let _ = sender.send(output);
+        });
+
+        let output = match receiver.recv_timeout(self.budget) {
+            Ok(output) => output,
+            Err(RecvTimeoutError::Timeout) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(Outcome::TimedOut(self.budget));
+            }
+            Err(RecvTimeoutError::Disconnected) => String::new(),
+        };
+
+        let panicked = Outcome::Panicked(started.elapsed());
+        if !child.wait().is_ok_and(|status| status.success()) {
+            return Err(panicked);
+        }
+
+        let (secs, answer) = output.trim_end().split_once(' ').ok_or(panicked)?;
+        let elapsed = secs
+            .parse()
+            .ok()
+            .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+            .ok_or(panicked)?;
+
+        Ok((answer.to_owned(), elapsed))
+    }
+
+    /// exits, with an error status if a part timed out or panicked
+    pub fn finish(&self) {
+        process::exit(self.failed.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a line like [`Budget::run`] prints it
+    fn line(answer: &str, outcome: Outcome) -> String {
+        format!("2015/day6/part1: {answer}{outcome}")
+    }
+
+    #[test]
+    fn outcomes_round_trip() {
+        let outcomes = [
+            Outcome::Done(Duration::from_millis(1234)),
+            Outcome::Done(Duration::ZERO),
+            Outcome::TimedOut(Duration::from_secs(10)),
+            Outcome::TimedOut(Duration::from_millis(2500)),
+            Outcome::Panicked(Duration::from_millis(17)),
+        ];
+
+        for outcome in outcomes {
+            let answer = match outcome {
+                Outcome::Done(_) => "Some(377891) ",
+                _ => "",
+            };
+            let line = line(answer, outcome);
+
+            assert_eq!(
+                Outcome::from_line(&line),
+                Some(("2015/day6/part1", outcome)),
+                "{line}"
+            );
+        }
+    }
+
+    #[test]
+    fn done_is_read_after_the_answer() {
+        let outcome = Outcome::Done(Duration::from_millis(5));
+        let line = line("\"done in 3s: panicked after 1s\" ", outcome);
+
+        assert_eq!(
+            Outcome::from_line(&line),
+            Some(("2015/day6/part1", outcome))
+        );
+    }
+
+    #[test]
+    fn times_are_rounded_to_milliseconds() {
+        let outcome = Outcome::Done(Duration::from_micros(1_234_567));
+        assert_eq!(outcome.to_string(), "in 1.235s");
+
+        let (_, read) = Outcome::from_line(&line("1 ", outcome)).unwrap();
+        assert_eq!(read, Outcome::Done(Duration::from_millis(1235)));
+    }
+
+    #[test]
+    fn other_lines_are_not_outcomes() {
+        for line in [
+            "",
+            "377891",
+            "2015/day6/part1: 1 in s",
+            "2015/day6/part1: in soon",
+        ] {
+            assert_eq!(Outcome::from_line(line), None, "{line}");
+        }
+    }
+}
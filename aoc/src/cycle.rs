@@ -0,0 +1,184 @@
+//! cycle detection on sequences `x0, f(x0), f(f(x0)), ...` where `f` is deterministic.
+//! any such sequence over a finite set of states ends up going in circles,
+//! a simulation that stops can be modelled with an absorbing state (e.g. `None`)
+
+use std::{collections::HashMap, hash::Hash};
+
+/// where the sequence starts repeating
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<S> {
+    /// steps before the first state of the cycle
+    pub prefix: usize,
+    /// steps to go around the cycle once
+    pub length: usize,
+    /// first state of the cycle, reached after `prefix` steps
+    pub start: S,
+}
+
+impl<S: Clone> Cycle<S> {
+    /// the states of one lap around the cycle, starting from `start`
+    pub fn states<'a>(&'a self, mut f: impl FnMut(&S) -> S + 'a) -> impl Iterator<Item = S> + 'a {
+        std::iter::successors(Some(self.start.clone()), move |s| Some(f(s))).take(self.length)
+    }
+}
+
+/// Brent's algorithm: needs only a couple of states in memory, at the cost of calling `f`
+/// a few more times than `hashed`
+pub fn brent<S: Clone + Eq>(x0: S, mut f: impl FnMut(&S) -> S) -> Cycle<S> {
+    // find the length, by moving `hare` ahead until it meets `tortoise`,
+    // which teleports to `hare` every power of two steps
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = x0.clone();
+    let mut hare = f(&x0);
+
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+
+        hare = f(&hare);
+        length += 1;
+    }
+
+    // find the prefix, by moving both at the same speed with `hare` one lap ahead
+    let mut tortoise = x0.clone();
+    let mut hare = x0;
+    for _ in 0..length {
+        hare = f(&hare);
+    }
+
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        prefix += 1;
+    }
+
+    Cycle {
+        prefix,
+        length,
+        start: tortoise,
+    }
+}
+
+/// remembers every state, so `f` is called exactly `prefix + length` times.
+/// also returns all the states met: `prefix` ones before the cycle, then one lap of it
+pub fn hashed<S: Clone + Eq + Hash>(x0: S, mut f: impl FnMut(&S) -> S) -> (Cycle<S>, Vec<S>) {
+    let mut seen = HashMap::new();
+    let mut history = Vec::new();
+    let mut curr = x0;
+
+    let prefix = loop {
+        if let Some(&first) = seen.get(&curr) {
+            break first;
+        }
+
+        seen.insert(curr.clone(), history.len());
+        let next = f(&curr);
+        history.push(curr);
+        curr = next;
+    };
+
+    let cycle = Cycle {
+        prefix,
+        length: history.len() - prefix,
+        start: curr,
+    };
+
+    (cycle, history)
+}
+
+/// the state after `n` steps. only simulates until the first repetition,
+/// then skips as many whole laps of the cycle as needed
+pub fn nth_state<S: Clone + Eq + Hash>(x0: S, mut f: impl FnMut(&S) -> S, n: u64) -> S {
+    let mut seen = HashMap::new();
+    let mut history = Vec::new();
+    let mut curr = x0;
+
+    for step in 0..n {
+        if let Some(&prefix) = seen.get(&curr) {
+            let length = step - prefix as u64;
+            let offset = (n - prefix as u64) % length;
+
+            return history.swap_remove(prefix + offset as usize);
+        }
+
+        seen.insert(curr.clone(), history.len());
+        let next = f(&curr);
+        history.push(curr);
+        curr = next;
+    }
+
+    curr
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0, 1, 2, 0, 1, 2, ...
+    fn no_tail(x: &u32) -> u32 {
+        (x + 1) % 3
+    }
+
+    /// 0, 1, 2, 3, 4, 5, 6, 3, 4, 5, 6, ...: a tail of 3, then a cycle of 4
+    fn tail(x: &u32) -> u32 {
+        match x {
+            6 => 3,
+            x => x + 1,
+        }
+    }
+
+    #[test]
+    fn cycles_from_the_first_state() {
+        let cycle = Cycle {
+            prefix: 0,
+            length: 3,
+            start: 0,
+        };
+
+        assert_eq!(brent(0, no_tail), cycle);
+        assert_eq!(hashed(0, no_tail), (cycle.clone(), vec![0, 1, 2]));
+        assert_eq!(cycle.states(no_tail).collect::<Vec<_>>(), [0, 1, 2]);
+
+        // a state that goes to itself
+        let fixed = Cycle {
+            prefix: 0,
+            length: 1,
+            start: 7,
+        };
+        assert_eq!(brent(7, |&x| x), fixed);
+        assert_eq!(hashed(7, |&x| x).0, fixed);
+    }
+
+    #[test]
+    fn cycles_after_a_tail() {
+        let cycle = Cycle {
+            prefix: 3,
+            length: 4,
+            start: 3,
+        };
+
+        assert_eq!(brent(0, tail), cycle);
+        assert_eq!(hashed(0, tail), (cycle.clone(), (0..7).collect()));
+        assert_eq!(cycle.states(tail).collect::<Vec<_>>(), [3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn nth_states_skip_whole_laps() {
+        let naive = |n| (0..n).fold(0, |x, _| tail(&x));
+
+        // below, at and past the tail, and far enough to go around many times
+        for n in [0, 1, 2, 3, 4, 6, 7, 8, 11, 100, 1001] {
+            assert_eq!(nth_state(0, tail, n), naive(n), "{n} steps");
+        }
+        for n in [0, 2, 3, 4, 1000] {
+            assert_eq!(nth_state(0, no_tail, n), n as u32 % 3, "{n} steps");
+        }
+        let n = 1_000_000_000_000;
+        assert_eq!(nth_state(0, tail, n) as u64, 3 + (n - 3) % 4);
+    }
+}
@@ -0,0 +1,255 @@
+//! differential testing: when a part can be solved in more than one way, they should all give
+//! the same answer. [`check`] runs them on many inputs, and if they ever disagree, shrinks that
+//! input to a small one where they still do, to have something to debug by hand
+
+use std::fmt::{self, Debug};
+
+use crate::generate::{self, Rng};
+
+/// a way to solve a part, and its name for the reports
+pub type Variant<'a, O> = (&'a str, &'a dyn Fn(&str) -> O);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mismatch<O> {
+    /// which of the inputs given to `check` it was
+    pub index: usize,
+    /// shrunk as far as it would go
+    pub input: String,
+    /// the answer of every variant to `input`
+    pub outputs: Vec<(String, O)>,
+}
+
+impl<O: Debug> fmt::Display for Mismatch<O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "the variants disagree on input {}, shrunk to:",
+            self.index
+        )?;
+        writeln!(f, "{}", self.input.trim_end())?;
+
+        for (name, output) in &self.outputs {
+            writeln!(f, "{name}: {output:?}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// `None` if every variant gives the same answer
+fn disagreement<O: PartialEq>(input: &str, variants: &[Variant<O>]) -> Option<Vec<(String, O)>> {
+    let outputs = variants
+        .iter()
+        .map(|(name, solve)| (name.to_string(), solve(input)))
+        .collect::<Vec<_>>();
+
+    outputs
+        .iter()
+        .any(|(_, output)| *output != outputs[0].1)
+        .then_some(outputs)
+}
+
+/// runs the variants on every input, until they disagree. then tries the inputs made by `shrink`
+/// from it, moving on to the first one where they still disagree, until none of them do.
+/// `Ok` with the number of inputs they agreed on
+pub fn check<O: PartialEq>(
+    inputs: impl IntoIterator<Item = String>,
+    variants: &[Variant<O>],
+    shrink: impl Fn(&str) -> Vec<String>,
+) -> Result<usize, Mismatch<O>> {
+    let mut checked = 0;
+
+    for (index, mut input) in inputs.into_iter().enumerate() {
+        let Some(mut outputs) = disagreement(&input, variants) else {
+            checked += 1;
+            continue;
+        };
+
+        while let Some((smaller, smaller_outputs)) =
+            shrink(&input).into_iter().find_map(|candidate| {
+                let outputs = disagreement(&candidate, variants)?;
+                Some((candidate, outputs))
+            })
+        {
+            input = smaller;
+            outputs = smaller_outputs;
+        }
+
+        return Err(Mismatch {
+            index,
+            input,
+            outputs,
+        });
+    }
+
+    Ok(checked)
+}
+
+/// the `variants` test of a day: [`check`] on its input files, then on what its generator makes
+/// of `size` (see [`generate::inputs`]), panicking with the [`Mismatch`] if they disagree
+pub fn assert_agree<O: PartialEq + Debug>(
+    files: &[&str],
+    generate: impl Fn(&mut Rng, usize) -> String,
+    size: usize,
+    variants: &[Variant<O>],
+    shrink: impl Fn(&str) -> Vec<String>,
+) {
+    let inputs = files
+        .iter()
+        .map(|&file| file.to_owned())
+        .chain(generate::inputs(generate, size).map(|(_, input)| input));
+
+    if let Err(mismatch) = check(inputs, variants, shrink) {
+        panic!("{mismatch}");
+    }
+}
+
+/// `input` without a block of lines: its halves first, then its quarters, down to single lines
+pub fn remove_lines(input: &str) -> Vec<String> {
+    let lines = input.lines().collect::<Vec<_>>();
+    let mut candidates = Vec::new();
+    let mut chunk = lines.len() / 2;
+
+    while chunk > 0 {
+        for start in (0..lines.len()).step_by(chunk) {
+            let end = lines.len().min(start + chunk);
+            let kept = [&lines[..start], &lines[end..]].concat();
+            candidates.push(kept.join("\n") + "\n");
+        }
+        chunk /= 2;
+    }
+
+    candidates
+}
+
+/// `input` without one of the fields of a line, if it has more than one
+pub fn remove_fields(input: &str, separator: char) -> Vec<String> {
+    let lines = input.lines().collect::<Vec<_>>();
+    let mut candidates = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        let fields = line.split(separator).collect::<Vec<_>>();
+        if fields.len() < 2 {
+            continue;
+        }
+
+        for j in 0..fields.len() {
+            let line = [&fields[..j], &fields[j + 1..]]
+                .concat()
+                .join(&separator.to_string());
+            let kept = [&lines[..i], &[line.as_str()], &lines[i + 1..]].concat();
+            candidates.push(kept.join("\n") + "\n");
+        }
+    }
+
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the sum of every number
+    fn sum(input: &str) -> u32 {
+        input
+            .split_whitespace()
+            .map(|n| n.parse::<u32>().unwrap())
+            .sum()
+    }
+
+    /// the same, but it skips 13
+    fn unlucky_sum(input: &str) -> u32 {
+        input
+            .split_whitespace()
+            .map(|n| n.parse::<u32>().unwrap())
+            .filter(|&n| n != 13)
+            .sum()
+    }
+
+    /// the number of lines, or 0 past 3 of them
+    fn few_lines(input: &str) -> usize {
+        let lines = input.lines().count();
+        if lines > 3 {
+            0
+        } else {
+            lines
+        }
+    }
+
+    fn lines(input: &str) -> usize {
+        input.lines().count()
+    }
+
+    fn lines_and_fields(input: &str) -> Vec<String> {
+        [remove_lines(input), remove_fields(input, ' ')].concat()
+    }
+
+    #[test]
+    fn agreeing_variants() {
+        let inputs = ["1 2\n3\n", "4 5 6\n"].map(str::to_owned);
+        let variants: [Variant<_>; 2] = [("sum", &sum), ("again", &|input| sum(input))];
+
+        assert_eq!(check(inputs, &variants, lines_and_fields), Ok(2));
+    }
+
+    #[test]
+    fn shrunk_to_the_field_they_disagree_on() {
+        let inputs = ["1 2\n3\n", "4 5 6\n7 8 13 9\n10 11\n12\n", "13\n"].map(str::to_owned);
+        let variants: [Variant<_>; 2] = [("sum", &sum), ("unlucky_sum", &unlucky_sum)];
+
+        assert_eq!(
+            check(inputs, &variants, lines_and_fields),
+            Err(Mismatch {
+                index: 1,
+                input: "13\n".to_owned(),
+                outputs: vec![("sum".to_owned(), 13), ("unlucky_sum".to_owned(), 0)],
+            })
+        );
+    }
+
+    #[test]
+    fn shrunk_to_the_fewest_lines_they_disagree_on() {
+        let input = (1..=10).map(|n| format!("{n}\n")).collect::<String>();
+        let variants: [Variant<_>; 2] = [("lines", &lines), ("few_lines", &few_lines)];
+
+        let mismatch = check([input], &variants, remove_lines).unwrap_err();
+        assert_eq!(mismatch.input.lines().count(), 4);
+        assert_eq!(
+            mismatch.outputs,
+            vec![("lines".to_owned(), 4), ("few_lines".to_owned(), 0)]
+        );
+    }
+
+    #[test]
+    fn the_mismatch_names_the_variants() {
+        let variants: [Variant<_>; 2] = [("sum", &sum), ("unlucky_sum", &unlucky_sum)];
+        let mismatch = check(["13\n".to_owned()], &variants, lines_and_fields).unwrap_err();
+
+        assert_eq!(
+            mismatch.to_string(),
+            "the variants disagree on input 0, shrunk to:\n13\nsum: 13\nunlucky_sum: 0\n"
+        );
+    }
+
+    #[test]
+    fn lines_removed_by_halves() {
+        assert_eq!(
+            remove_lines("a\nb\nc\nd\n"),
+            [
+                "c\nd\n",
+                "a\nb\n",
+                "b\nc\nd\n",
+                "a\nc\nd\n",
+                "a\nb\nd\n",
+                "a\nb\nc\n"
+            ]
+        );
+        assert!(remove_lines("a\n").is_empty());
+    }
+
+    #[test]
+    fn fields_removed_one_at_a_time() {
+        assert_eq!(remove_fields("1,2\n3\n", ','), ["2\n3\n", "1\n3\n"]);
+        assert!(remove_fields("1\n2\n", ',').is_empty());
+    }
+}
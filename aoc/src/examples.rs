@@ -0,0 +1,27 @@
+//! the answers of the examples in the puzzle text, checked by `cargo test`.
+//!
+//! every day keeps them in `src/examples.rs`, next to the example files, declared in `main.rs`
+//! with `#[cfg(test)] mod examples;`. each line becomes a `#[test]` with that name:
+//!
+//! ```ignore
+//! aoc::examples! {
+//!     part1_input0: part1(include_str!("input0.txt")) => Some(143),
+//!     part2_input0: part2(include_str!("input0.txt")) => Some(123),
+//! }
+//! ```
+
+/// see the module documentation
+#[macro_export]
+macro_rules! examples {
+    ($($name:ident: $call:expr => $expected:expr),* $(,)?) => {
+        #[allow(unused_imports)]
+        use super::*;
+
+        $(
+            #[test]
+            fn $name() {
+                assert_eq!($call, $expected);
+            }
+        )*
+    };
+}
@@ -0,0 +1,246 @@
+//! random puzzle inputs, to try the solutions on more than the one input we got.
+//!
+//! a day with a `generate` module swaps its input for a generated one when started with
+//! `--generate SIZE`, what the size counts depends on the day. other flags:
+//! - `--seed N`: to get the same input again, a random one is printed otherwise
+//! - `--print`: print the input and exit, instead of solving it
+//!
+//! with `--bench` too, that times the solutions on inputs of any size
+
+use std::{
+    env, iter,
+    ops::Range,
+    process,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// SplitMix64: fast, and good enough for inputs
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// uniform in `0..n`, panics if `n` is 0
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "empty range");
+
+        // drop the values past the last multiple of `n`, they'd make the small numbers likelier
+        let limit = u64::MAX - u64::MAX % n;
+        iter::repeat_with(|| self.next_u64())
+            .find(|&x| x < limit)
+            .unwrap()
+            % n
+    }
+
+    /// panics if `range` is empty
+    pub fn range(&mut self, range: Range<u64>) -> u64 {
+        range.start + self.below(range.end - range.start)
+    }
+
+    /// `true` with probability `p`
+    pub fn chance(&mut self, p: f64) -> bool {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        unit < p
+    }
+
+    /// panics if `items` is empty
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    /// Fisher-Yates
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+/// how many inputs [`inputs`] makes
+pub const SEEDS: u64 = 20;
+
+/// what `generate` makes of `size` with the seeds `0..SEEDS`, along with the seed, for the tests
+/// of a generator and of the solutions on what it makes
+pub fn inputs(
+    generate: impl Fn(&mut Rng, usize) -> String,
+    size: usize,
+) -> impl Iterator<Item = (u64, String)> {
+    (0..SEEDS).map(move |seed| (seed, generate(&mut Rng::new(seed), size)))
+}
+
+/// `default`, unless the program was started with `--generate SIZE`, then what `generate` makes
+pub fn input_or(
+    default: &'static str,
+    generate: impl FnOnce(&mut Rng, usize) -> String,
+) -> &'static str {
+    let mut size = None;
+    let mut seed = None;
+    let mut print = false;
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--generate" => {
+                size = Some(
+                    args.next()
+                        .and_then(|n| n.parse().ok())
+                        .expect("--generate SIZE"),
+                )
+            }
+            "--seed" => seed = Some(args.next().and_then(|n| n.parse().ok()).expect("--seed N")),
+            "--print" => print = true,
+            _ => (),
+        }
+    }
+
+    let Some(size) = size else {
+        return default;
+    };
+
+    let seed = seed.unwrap_or_else(|| {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let seed = now.as_nanos() as u64;
+        eprintln!("--seed {seed}");
+        seed
+    });
+
+    let input = generate(&mut Rng::new(seed), size);
+    if print {
+        print!("{input}");
+        process::exit(0);
+    }
+
+    // lives as long as the program anyway, like the inputs that are included
+    input.leak()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..100).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+
+        // the reference SplitMix64, so that a seed keeps making the same input
+        assert_eq!(numbers(0)[..2], [0xe220a8397b1dcdaf, 0x6e789e6aa1b965f4]);
+        assert_eq!(numbers(7), numbers(7));
+        assert_ne!(numbers(7), numbers(8));
+
+        let mut rng = Rng::new(7);
+        let mut again = rng.clone();
+        assert_eq!(rng.below(1000), again.below(1000));
+    }
+
+    #[test]
+    fn below_stays_below() {
+        let mut rng = Rng::new(0);
+
+        for n in [1, 2, 3, 7, 10, 1000, u64::MAX / 3 + 1, u64::MAX] {
+            for _ in 0..1000 {
+                assert!(rng.below(n) < n, "{n}");
+            }
+        }
+    }
+
+    #[test]
+    fn below_reaches_every_value() {
+        let mut rng = Rng::new(0);
+        let mut seen = [0; 6];
+
+        for _ in 0..6000 {
+            seen[rng.below(6) as usize] += 1;
+        }
+
+        // a thousand each on average
+        assert!(seen.iter().all(|&n| (800..1200).contains(&n)), "{seen:?}");
+    }
+
+    #[test]
+    #[should_panic(expected = "empty range")]
+    fn below_nothing() {
+        Rng::new(0).below(0);
+    }
+
+    #[test]
+    fn ranges() {
+        let mut rng = Rng::new(0);
+
+        for _ in 0..1000 {
+            assert!((5..8).contains(&rng.range(5..8)));
+        }
+        assert_eq!(rng.range(3..4), 3);
+    }
+
+    #[test]
+    fn chances() {
+        let mut rng = Rng::new(0);
+
+        assert!((0..1000).all(|_| !rng.chance(0.)));
+        assert!((0..1000).all(|_| rng.chance(1.)));
+
+        let hits = (0..10_000).filter(|_| rng.chance(0.25)).count();
+        assert!((2300..2700).contains(&hits), "{hits}");
+    }
+
+    #[test]
+    fn shuffles_are_permutations() {
+        let mut rng = Rng::new(0);
+
+        for len in [0, 1, 2, 10, 100] {
+            let mut items = (0..len).collect::<Vec<_>>();
+            rng.shuffle(&mut items);
+
+            let mut sorted = items.clone();
+            sorted.sort_unstable();
+            assert_eq!(sorted, (0..len).collect::<Vec<_>>());
+        }
+
+        // and they do move things around
+        let mut items = (0..100).collect::<Vec<_>>();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn shuffles_depend_on_the_seed() {
+        let shuffled = |seed| {
+            let mut items = (0..20).collect::<Vec<_>>();
+            Rng::new(seed).shuffle(&mut items);
+            items
+        };
+
+        assert_eq!(shuffled(3), shuffled(3));
+        assert_ne!(shuffled(3), shuffled(4));
+    }
+
+    #[test]
+    fn inputs_of_every_seed() {
+        let inputs = inputs(|rng, size| format!("{size} {}", rng.next_u64()), 5);
+        let inputs = inputs.collect::<Vec<_>>();
+
+        assert_eq!(inputs.len(), SEEDS as usize);
+        for (seed, input) in inputs {
+            assert_eq!(input, format!("5 {}", Rng::new(seed).next_u64()));
+        }
+    }
+}
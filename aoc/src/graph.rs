@@ -0,0 +1,548 @@
+//! graph algorithms over implicit graphs.
+//!
+//! nodes can be any `Clone + Eq + Hash` id, and edges are given by a `successors` closure,
+//! so the graph never needs to be built upfront
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// walk `parents` back from `end` and return the path in forward order
+fn rebuild_path<N: Clone + Eq + Hash>(parents: &HashMap<N, N>, end: N) -> Vec<N> {
+    let mut path = vec![end];
+
+    while let Some(parent) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+
+    path.reverse();
+    path
+}
+
+/// same as `rebuild_path`, for algorithms that keep nodes in a `Vec` and refer to them by index
+fn rebuild_path_indexed<N: Clone>(nodes: &[N], parents: &[usize], end: usize) -> Vec<N> {
+    let mut path = vec![nodes[end].clone()];
+    let mut curr = end;
+
+    while parents[curr] != curr {
+        curr = parents[curr];
+        path.push(nodes[curr].clone());
+    }
+
+    path.reverse();
+    path
+}
+
+/// breadth-first search from `start`. returns the shortest path (in number of edges) to the
+/// first node satisfying `is_goal`, `start` and goal included
+pub fn bfs<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents = HashMap::new();
+    let mut seen = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(rebuild_path(&parents, node));
+        }
+
+        for next in successors(&node) {
+            if seen.insert(next.clone()) {
+                parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// depth-first search from `start`. returns the path it took to the first node satisfying
+/// `is_goal`, which is not necessarily the shortest one
+pub fn dfs<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    if is_goal(&start) {
+        return Some(vec![start]);
+    }
+
+    let mut seen = HashSet::from([start.clone()]);
+    let mut path = vec![start.clone()];
+    let mut stack = vec![successors(&start).into_iter()];
+
+    while let Some(children) = stack.last_mut() {
+        match children.find(|next| !seen.contains(next)) {
+            Some(next) => {
+                if is_goal(&next) {
+                    path.push(next);
+                    return Some(path);
+                }
+
+                seen.insert(next.clone());
+                stack.push(successors(&next).into_iter());
+                path.push(next);
+            }
+            None => {
+                stack.pop();
+                path.pop();
+            }
+        }
+    }
+
+    None
+}
+
+/// shortest path from `start` to the first node satisfying `is_goal`, with non-negative edge costs.
+/// returns the path and its total cost
+pub fn dijkstra<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// A* search from `start` to the first node satisfying `is_goal`.
+/// `heuristic` must never overestimate the remaining cost, otherwise the path may not be the shortest
+pub fn astar<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    // nodes are referred to by index, so the heap doesn't need `N: Ord`
+    let mut nodes = vec![start.clone()];
+    let mut indexes = HashMap::from([(start.clone(), 0)]);
+    let mut parents = vec![0];
+    let mut costs = vec![C::default()];
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+
+    while let Some(Reverse((_, cost, curr))) = heap.pop() {
+        // stale entry, a cheaper way to `curr` was found after it was pushed
+        if cost > costs[curr] {
+            continue;
+        }
+
+        if is_goal(&nodes[curr]) {
+            return Some((rebuild_path_indexed(&nodes, &parents, curr), cost));
+        }
+
+        for (next, step) in successors(&nodes[curr].clone()) {
+            let next_cost = cost + step;
+
+            let next_idx = match indexes.get(&next) {
+                Some(&idx) if costs[idx] <= next_cost => continue,
+                Some(&idx) => {
+                    costs[idx] = next_cost;
+                    parents[idx] = curr;
+                    idx
+                }
+                None => {
+                    let idx = nodes.len();
+                    indexes.insert(next.clone(), idx);
+                    nodes.push(next);
+                    parents.push(curr);
+                    costs.push(next_cost);
+                    idx
+                }
+            };
+
+            let estimate = next_cost + heuristic(&nodes[next_idx]);
+            heap.push(Reverse((estimate, next_cost, next_idx)));
+        }
+    }
+
+    None
+}
+
+/// every node reachable from `roots`, with edges stored by index
+struct Indexed<N, C> {
+    nodes: Vec<N>,
+    edges: Vec<Vec<(usize, C)>>,
+}
+
+impl<N: Clone + Eq + Hash, C> Indexed<N, C> {
+    fn explore<I>(roots: impl IntoIterator<Item = N>, mut successors: impl FnMut(&N) -> I) -> Self
+    where
+        I: IntoIterator<Item = (N, C)>,
+    {
+        let mut nodes = Vec::new();
+        let mut indexes = HashMap::new();
+        let mut edges = Vec::new();
+
+        let mut index_of = |node: N, nodes: &mut Vec<N>| {
+            *indexes.entry(node.clone()).or_insert_with(|| {
+                nodes.push(node);
+                nodes.len() - 1
+            })
+        };
+
+        for root in roots {
+            index_of(root, &mut nodes);
+        }
+
+        let mut curr = 0;
+        while curr < nodes.len() {
+            let node_edges = successors(&nodes[curr].clone())
+                .into_iter()
+                .map(|(next, weight)| (index_of(next, &mut nodes), weight))
+                .collect();
+
+            edges.push(node_edges);
+            curr += 1;
+        }
+
+        Indexed { nodes, edges }
+    }
+
+    /// Kahn's algorithm. on failure, returns the indexes of a cycle
+    fn topological_order(&self) -> Result<Vec<usize>, Vec<usize>> {
+        let mut in_degree = vec![0; self.nodes.len()];
+
+        for &(next, _) in self.edges.iter().flatten() {
+            in_degree[next] += 1;
+        }
+
+        let mut queue = (0..self.nodes.len())
+            .filter(|&idx| in_degree[idx] == 0)
+            .collect::<VecDeque<_>>();
+        let mut sorted = Vec::with_capacity(self.nodes.len());
+
+        while let Some(curr) = queue.pop_front() {
+            sorted.push(curr);
+
+            for &(next, _) in &self.edges[curr] {
+                in_degree[next] -= 1;
+
+                if in_degree[next] == 0 {
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        if sorted.len() == self.nodes.len() {
+            Ok(sorted)
+        } else {
+            Err(self.find_cycle(&in_degree))
+        }
+    }
+
+    /// nodes left with a positive in-degree by Kahn's algorithm are all either on a cycle or
+    /// downstream of one. walking backwards through them always ends up going in circles
+    fn find_cycle(&self, in_degree: &[usize]) -> Vec<usize> {
+        let mut predecessor = vec![None; self.nodes.len()];
+
+        for (curr, edges) in self.edges.iter().enumerate() {
+            for &(next, _) in edges {
+                if in_degree[curr] > 0 && in_degree[next] > 0 {
+                    predecessor[next] = Some(curr);
+                }
+            }
+        }
+
+        let mut visited = vec![false; self.nodes.len()];
+        let mut curr = in_degree.iter().position(|&d| d > 0).unwrap();
+
+        while !visited[curr] {
+            visited[curr] = true;
+            curr = predecessor[curr].unwrap();
+        }
+
+        let mut cycle = vec![curr];
+        let mut prev = predecessor[curr].unwrap();
+        while prev != curr {
+            cycle.push(prev);
+            prev = predecessor[prev].unwrap();
+        }
+
+        cycle.reverse();
+        cycle
+    }
+
+    fn to_nodes(&self, indexes: impl IntoIterator<Item = usize>) -> Vec<N> {
+        indexes
+            .into_iter()
+            .map(|idx| self.nodes[idx].clone())
+            .collect()
+    }
+}
+
+/// topological order of every node reachable from `roots`.
+/// if the graph has a cycle, returns `Err` with the nodes of one of them, in edge order
+pub fn topological_sort<N, I>(
+    roots: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+) -> Result<Vec<N>, Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let graph = Indexed::explore(roots, |node| {
+        successors(node).into_iter().map(|next| (next, ()))
+    });
+
+    graph
+        .topological_order()
+        .map(|order| graph.to_nodes(order))
+        .map_err(|cycle| graph.to_nodes(cycle))
+}
+
+/// strongly connected components of every node reachable from `roots` (Tarjan's algorithm).
+/// components come out in reverse topological order, i.e. a component never has edges into
+/// the ones that come after it
+pub fn strongly_connected_components<N, I>(
+    roots: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+) -> Vec<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let graph = Indexed::explore(roots, |node| {
+        successors(node).into_iter().map(|next| (next, ()))
+    });
+
+    let len = graph.nodes.len();
+    let mut order = vec![usize::MAX; len];
+    let mut low_link = vec![0; len];
+    let mut on_stack = vec![false; len];
+    let mut stack = Vec::new();
+    let mut components = Vec::new();
+    let mut counter = 0;
+
+    // the recursion is unrolled in `calls`, as (node, index of the next edge to look at)
+    let mut calls = Vec::new();
+
+    for root in 0..len {
+        if order[root] != usize::MAX {
+            continue;
+        }
+
+        calls.push((root, 0));
+
+        while let Some((curr, edge)) = calls.pop() {
+            if edge == 0 {
+                order[curr] = counter;
+                low_link[curr] = counter;
+                counter += 1;
+                stack.push(curr);
+                on_stack[curr] = true;
+            }
+
+            if let Some(&(next, _)) = graph.edges[curr].get(edge) {
+                calls.push((curr, edge + 1));
+
+                if order[next] == usize::MAX {
+                    calls.push((next, 0));
+                } else if on_stack[next] {
+                    low_link[curr] = low_link[curr].min(order[next]);
+                }
+                continue;
+            }
+
+            // every edge of `curr` is done, propagate to the caller
+            if let Some(&(parent, _)) = calls.last() {
+                low_link[parent] = low_link[parent].min(low_link[curr]);
+            }
+
+            if low_link[curr] == order[curr] {
+                let mut component = Vec::new();
+
+                while let Some(node) = stack.pop() {
+                    on_stack[node] = false;
+                    component.push(node);
+
+                    if node == curr {
+                        break;
+                    }
+                }
+
+                components.push(graph.to_nodes(component));
+            }
+        }
+    }
+
+    components
+}
+
+/// heaviest path through the DAG reachable from `roots`, along with its total weight.
+/// returns `Ok(None)` if there are no nodes, and `Err` with the nodes of a cycle if it's not a DAG
+#[allow(clippy::type_complexity)]
+pub fn longest_path<N, C, I>(
+    roots: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+) -> Result<Option<(Vec<N>, C)>, Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let graph = Indexed::explore(roots, successors);
+    let order = graph
+        .topological_order()
+        .map_err(|cycle| graph.to_nodes(cycle))?;
+
+    let len = graph.nodes.len();
+    let mut best = vec![C::default(); len];
+    let mut parents = (0..len).collect::<Vec<_>>();
+
+    for &curr in &order {
+        for &(next, weight) in &graph.edges[curr] {
+            let candidate = best[curr] + weight;
+
+            if candidate > best[next] {
+                best[next] = candidate;
+                parents[next] = curr;
+            }
+        }
+    }
+
+    let longest = (0..len)
+        .max_by_key(|&idx| best[idx])
+        .map(|end| (rebuild_path_indexed(&graph.nodes, &parents, end), best[end]));
+
+    Ok(longest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1 -> 2 -> 4, 1 -> 3 -> 4 -> 5, with weights on the edges
+    fn diamond(node: &u32) -> Vec<(u32, u32)> {
+        match node {
+            1 => vec![(2, 1), (3, 5)],
+            2 => vec![(4, 1)],
+            3 => vec![(4, 1)],
+            4 => vec![(5, 2)],
+            _ => vec![],
+        }
+    }
+
+    fn unweighted(node: &u32) -> Vec<u32> {
+        diamond(node).into_iter().map(|(next, _)| next).collect()
+    }
+
+    /// 1 -> 2 -> 3 -> 1, and 3 -> 4 -> 5 -> 4
+    fn loops(node: &u32) -> Vec<u32> {
+        match node {
+            1 => vec![2],
+            2 => vec![3],
+            3 => vec![1, 4],
+            4 => vec![5],
+            5 => vec![4],
+            _ => vec![],
+        }
+    }
+
+    /// whether `cycle` is a cycle of `successors`, each node leading to the next
+    fn is_cycle(cycle: &[u32], successors: impl Fn(&u32) -> Vec<u32>) -> bool {
+        !cycle.is_empty()
+            && (0..cycle.len())
+                .all(|i| successors(&cycle[i]).contains(&cycle[(i + 1) % cycle.len()]))
+    }
+
+    #[test]
+    fn searches_find_a_path() {
+        assert_eq!(bfs(1, unweighted, |&n| n == 5), Some(vec![1, 2, 4, 5]));
+        assert_eq!(bfs(1, unweighted, |&n| n == 1), Some(vec![1]));
+        assert_eq!(bfs(2, unweighted, |&n| n == 3), None);
+
+        let path = dfs(1, unweighted, |&n| n == 5).unwrap();
+        assert_eq!((path[0], path[path.len() - 1]), (1, 5));
+        assert_eq!(dfs(4, unweighted, |&n| n == 1), None);
+    }
+
+    #[test]
+    fn shortest_paths_weigh_the_edges() {
+        assert_eq!(
+            dijkstra(1, diamond, |&n| n == 5),
+            Some((vec![1, 2, 4, 5], 4))
+        );
+        assert_eq!(
+            astar(1, diamond, |&n| 5 - n.min(5), |&n| n == 5),
+            Some((vec![1, 2, 4, 5], 4))
+        );
+        assert_eq!(dijkstra(5, diamond, |&n| n == 1), None);
+    }
+
+    #[test]
+    fn topological_order_puts_nodes_before_their_successors() {
+        let order = topological_sort([1], unweighted).unwrap();
+        let position = |n| order.iter().position(|&m| m == n).unwrap();
+
+        assert_eq!(order.len(), 5);
+        for node in 1..=5 {
+            for next in unweighted(&node) {
+                assert!(position(node) < position(next), "{node} -> {next}");
+            }
+        }
+    }
+
+    #[test]
+    fn topological_sort_reports_a_cycle() {
+        let cycle = topological_sort([1], loops).unwrap_err();
+        assert!(is_cycle(&cycle, loops), "{cycle:?}");
+
+        let self_loop = |_: &u32| vec![7];
+        assert_eq!(topological_sort([7], self_loop), Err(vec![7]));
+    }
+
+    #[test]
+    fn longest_path_through_a_dag() {
+        assert_eq!(longest_path([1], diamond), Ok(Some((vec![1, 3, 4, 5], 8))));
+        assert_eq!(
+            longest_path(Vec::<u32>::new(), diamond),
+            Ok(None::<(Vec<u32>, u32)>)
+        );
+
+        let weighted_loops = |n: &u32| {
+            loops(n)
+                .into_iter()
+                .map(|next| (next, 1))
+                .collect::<Vec<_>>()
+        };
+        let cycle = longest_path::<_, u32, _>([1], weighted_loops).unwrap_err();
+        assert!(is_cycle(&cycle, loops), "{cycle:?}");
+    }
+
+    #[test]
+    fn components_come_in_reverse_topological_order() {
+        let mut components = strongly_connected_components([1], loops);
+        for component in &mut components {
+            component.sort_unstable();
+        }
+        assert_eq!(components, [vec![4, 5], vec![1, 2, 3]]);
+
+        let components = strongly_connected_components([1], unweighted);
+        assert_eq!(components.len(), 5);
+        assert_eq!(components[0], [5]);
+        assert_eq!(components[4], [1]);
+    }
+}
@@ -0,0 +1,339 @@
+//! pictures of grids, for the ones too big to look at as text.
+//!
+//! writes binary PPM, which anything can read, and PNG, left uncompressed to stay small and
+//! without dependencies. a day opts in from its `main` when started with `--images DIR`,
+//! and names the files it writes there
+
+use std::{
+    env, fs,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::anim::Frame;
+
+pub type Rgb = [u8; 3];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> Rgb) -> Self {
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// one pixel per cell of `grid`, stored row by row, coloured by `palette`
+    pub fn from_grid<T>(grid: &[T], width: usize, palette: impl FnMut(&T) -> Rgb) -> Self {
+        Image {
+            width,
+            height: grid.len() / width.max(1),
+            pixels: grid.iter().map(palette).collect(),
+        }
+    }
+
+    /// one pixel per cell, of the colour it has in the terminal
+    pub fn from_frame(frame: &Frame) -> Self {
+        Image::from_fn(frame.width(), frame.height(), |x, y| {
+            frame.get(x, y).map_or([0; 3], |cell| cell.color.rgb())
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// every pixel becomes a `factor` × `factor` square
+    pub fn scaled(&self, factor: usize) -> Self {
+        Image::from_fn(self.width * factor, self.height * factor, |x, y| {
+            self.pixels[x / factor + y / factor * self.width]
+        })
+    }
+
+    pub fn write_ppm(&self, mut out: impl Write) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(self.pixels.as_flattened())
+    }
+
+    pub fn write_png(&self, mut out: impl Write) -> io::Result<()> {
+        // every row starts with its filter, 0 for none
+        let mut raw = Vec::with_capacity((self.width * 3 + 1) * self.height);
+        for row in self.pixels.chunks(self.width.max(1)) {
+            raw.push(0);
+            raw.extend_from_slice(row.as_flattened());
+        }
+
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        // 8 bits per channel, RGB, then the only compression, filtering and non interlaced
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        out.write_all(b"\x89PNG\r\n\x1a\n")?;
+        write_chunk(&mut out, b"IHDR", &header)?;
+        write_chunk(&mut out, b"IDAT", &zlib_stored(&raw))?;
+        write_chunk(&mut out, b"IEND", &[])
+    }
+
+    /// PNG or PPM, depending on the extension of `path`
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let write = match path.extension().and_then(|e| e.to_str()) {
+            Some("png") => Image::write_png,
+            Some("ppm") => Image::write_ppm,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{} is neither .png nor .ppm", path.display()),
+                ))
+            }
+        };
+
+        let mut out = BufWriter::new(fs::File::create(path)?);
+        write(self, &mut out)?;
+        out.flush()
+    }
+}
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut n = 0;
+
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 == 1 {
+                0xedb88320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+
+    table
+}
+
+const CRC_TABLE: [u32; 256] = crc_table();
+
+fn crc32(parts: &[&[u8]]) -> u32 {
+    let crc = parts
+        .iter()
+        .flat_map(|part| part.iter())
+        .fold(!0, |crc, &b| {
+            CRC_TABLE[((crc ^ b as u32) & 0xff) as usize] ^ (crc >> 8)
+        });
+
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+
+    (b << 16) | a
+}
+
+fn write_chunk(out: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+    out.write_all(&crc32(&[kind, data]).to_be_bytes())
+}
+
+/// a zlib stream made of deflate blocks that are stored as is, up to 65535 bytes each
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = u16::MAX as usize;
+
+    let blocks = data.len().div_ceil(MAX_BLOCK).max(1);
+    let mut out = Vec::with_capacity(data.len() + blocks * 5 + 6);
+    // deflate with a 32K window, and the check bits that make the header a multiple of 31
+    out.extend_from_slice(&[0x78, 0x01]);
+
+    for i in 0..blocks {
+        let block = &data[i * MAX_BLOCK..data.len().min((i + 1) * MAX_BLOCK)];
+        let len = block.len() as u16;
+
+        out.push((i + 1 == blocks) as u8);
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+/// black for 0, then red, yellow and white for 1
+pub fn heatmap(t: f64) -> Rgb {
+    let t = if t.is_nan() { 0. } else { t.clamp(0., 1.) } * 3.;
+    let channel = |start: f64| ((t - start).clamp(0., 1.) * 255.).round() as u8;
+
+    [channel(0.), channel(1.), channel(2.)]
+}
+
+/// numbered files `00000.png`, `00001.png`, ... in a folder, for the frames of an animation
+pub struct Sequence {
+    dir: PathBuf,
+    scale: usize,
+    next: usize,
+}
+
+impl Sequence {
+    /// creates `dir` if needed. images are scaled by `scale` before being saved
+    pub fn new(dir: impl Into<PathBuf>, scale: usize) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+
+        Ok(Sequence {
+            dir,
+            scale: scale.max(1),
+            next: 0,
+        })
+    }
+
+    /// the path the image was saved at
+    pub fn save(&mut self, image: &Image) -> io::Result<PathBuf> {
+        let path = self.dir.join(format!("{:05}.png", self.next));
+        image.scaled(self.scale).save(&path)?;
+        self.next += 1;
+
+        Ok(path)
+    }
+}
+
+/// the folder given with `--images`, created if needed
+pub fn dir_from_args() -> Option<PathBuf> {
+    let mut args = env::args().skip(1);
+    args.find(|arg| arg == "--images")?;
+
+    let dir = PathBuf::from(args.next().expect("--images DIR"));
+    fs::create_dir_all(&dir).expect("couldn't create the images folder");
+
+    Some(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the data of a zlib stream of stored blocks, checking every header on the way
+    fn unstore(zlib: &[u8]) -> Vec<u8> {
+        assert_eq!(
+            u16::from_be_bytes([zlib[0], zlib[1]]) % 31,
+            0,
+            "zlib header"
+        );
+        let mut data = Vec::new();
+        let mut at = 2;
+
+        loop {
+            let last = zlib[at];
+            let len = u16::from_le_bytes([zlib[at + 1], zlib[at + 2]]);
+            let nlen = u16::from_le_bytes([zlib[at + 3], zlib[at + 4]]);
+            assert_eq!(len, !nlen, "block at {at}");
+            at += 5;
+
+            data.extend_from_slice(&zlib[at..at + len as usize]);
+            at += len as usize;
+            if last == 1 {
+                break;
+            }
+            assert_eq!(last, 0, "block header at {at}");
+        }
+
+        assert_eq!(zlib[at..], adler32(&data).to_be_bytes());
+        data
+    }
+
+    #[test]
+    fn checksums_match_the_reference_values() {
+        assert_eq!(crc32(&[b"123456789"]), 0xcbf43926);
+        assert_eq!(crc32(&[b"1234", b"", b"56789"]), 0xcbf43926);
+        assert_eq!(crc32(&[]), 0);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e60398);
+        assert_eq!(adler32(&[]), 1);
+    }
+
+    #[test]
+    fn adler32_wraps_around_on_long_data() {
+        let data = (0..100_000)
+            .map(|i| (i * 7 % 256) as u8)
+            .collect::<Vec<_>>();
+        let (mut a, mut b) = (1u64, 0u64);
+        for &byte in &data {
+            a += byte as u64;
+            b += a;
+        }
+
+        assert_eq!(adler32(&data), (((b % 65521) << 16) | (a % 65521)) as u32);
+    }
+
+    #[test]
+    fn a_single_pixel_png() {
+        let mut png = Vec::new();
+        Image::from_fn(1, 1, |_, _| [255, 0, 0])
+            .write_png(&mut png)
+            .unwrap();
+
+        let (signature, rest) = png.split_at(8);
+        assert_eq!(signature, b"\x89PNG\r\n\x1a\n");
+
+        let mut chunks = Vec::new();
+        let mut rest = rest;
+        while !rest.is_empty() {
+            let len = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+            let (kind, data) = (&rest[4..8], &rest[8..8 + len]);
+            let crc = u32::from_be_bytes(rest[8 + len..12 + len].try_into().unwrap());
+            assert_eq!(crc, crc32(&[kind, data]), "crc of {kind:?}");
+
+            chunks.push((kind, data));
+            rest = &rest[12 + len..];
+        }
+
+        let kinds = chunks.iter().map(|(kind, _)| *kind).collect::<Vec<_>>();
+        assert_eq!(kinds, [b"IHDR", b"IDAT", b"IEND"]);
+        assert_eq!(chunks[0].1, [0, 0, 0, 1, 0, 0, 0, 1, 8, 2, 0, 0, 0]);
+        // the filter byte of the only row, then its pixel
+        assert_eq!(unstore(chunks[1].1), [0, 255, 0, 0]);
+        assert_eq!(chunks[2].1, []);
+        assert_eq!(png.len(), 8 + 25 + 27 + 12);
+    }
+
+    #[test]
+    fn long_data_is_split_in_blocks() {
+        let data = (0..150_000).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        let zlib = zlib_stored(&data);
+
+        // 65535 + 65535 + 18930
+        assert_eq!(zlib.len(), 2 + 3 * 5 + data.len() + 4);
+        assert_eq!(zlib[2], 0);
+        assert_eq!(zlib[2 + 5 + 65535], 0);
+        assert_eq!(zlib[2 + 2 * (5 + 65535)], 1);
+        assert_eq!(unstore(&zlib), data);
+
+        // one empty block for no data at all
+        assert_eq!(unstore(&zlib_stored(&[])), []);
+    }
+}
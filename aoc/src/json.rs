@@ -0,0 +1,336 @@
+//! just enough JSON to save and load our own files, without dependencies
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    /// keeps the order fields were written in
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// inserts `key`, or replaces it if already there. does nothing if `self` is not an object
+    pub fn set(&mut self, key: &str, value: Value) {
+        if let Value::Object(fields) = self {
+            match fields.iter_mut().find(|(k, _)| k == key) {
+                Some((_, v)) => *v = value,
+                None => fields.push((key.to_owned(), value)),
+            }
+        }
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.to_owned())
+    }
+}
+
+impl From<f64> for Value {
+    fn from(n: f64) -> Self {
+        Value::Number(n)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Value::Null, Into::into)
+    }
+}
+
+/// writes `s` as a JSON string, quotes included
+pub fn write_str(f: &mut impl fmt::Write, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+
+    f.write_char('"')
+}
+
+/// compact, on a single line
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => f.write_str("null"),
+            Value::Bool(b) => write!(f, "{b}"),
+            Value::Number(n) if n.is_finite() => write!(f, "{n}"),
+            Value::Number(_) => f.write_str("null"),
+            Value::String(s) => write_str(f, s),
+            Value::Array(values) => {
+                f.write_str("[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_str("]")
+            }
+            Value::Object(fields) => {
+                f.write_str("{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write_str(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn rest(&self) -> &str {
+        &self.input[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, token: &str) -> Option<()> {
+        self.skip_whitespace();
+        self.rest()
+            .starts_with(token)
+            .then(|| self.pos += token.len())
+    }
+
+    fn value(&mut self) -> Option<Value> {
+        self.skip_whitespace();
+
+        match self.rest().chars().next()? {
+            'n' => self.eat("null").map(|_| Value::Null),
+            't' => self.eat("true").map(|_| Value::Bool(true)),
+            'f' => self.eat("false").map(|_| Value::Bool(false)),
+            '"' => self.string().map(Value::String),
+            '[' => self.array(),
+            '{' => self.object(),
+            _ => self.number(),
+        }
+    }
+
+    fn number(&mut self) -> Option<Value> {
+        let len = self
+            .rest()
+            .find(|c: char| !matches!(c, '0'..='9' | '-' | '+' | '.' | 'e' | 'E'))
+            .unwrap_or(self.rest().len());
+        let n = self.rest()[..len].parse().ok()?;
+        self.pos += len;
+
+        Some(Value::Number(n))
+    }
+
+    fn string(&mut self) -> Option<String> {
+        self.eat("\"")?;
+        let mut s = String::new();
+        let mut chars = self.rest().char_indices();
+
+        loop {
+            let (i, c) = chars.next()?;
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Some(s);
+                }
+                '\\' => match chars.next()?.1 {
+                    'n' => s.push('\n'),
+                    'r' => s.push('\r'),
+                    't' => s.push('\t'),
+                    'b' => s.push('\u{8}'),
+                    'f' => s.push('\u{c}'),
+                    'u' => {
+                        let hex = (0..4)
+                            .map(|_| chars.next().map(|(_, c)| c))
+                            .collect::<Option<String>>()?;
+                        s.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+                    }
+                    c => s.push(c),
+                },
+                c => s.push(c),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Option<Value> {
+        self.eat("[")?;
+        let mut values = Vec::new();
+
+        if self.eat("]").is_some() {
+            return Some(Value::Array(values));
+        }
+
+        loop {
+            values.push(self.value()?);
+
+            if self.eat("]").is_some() {
+                return Some(Value::Array(values));
+            }
+            self.eat(",")?;
+        }
+    }
+
+    fn object(&mut self) -> Option<Value> {
+        self.eat("{")?;
+        let mut fields = Vec::new();
+
+        if self.eat("}").is_some() {
+            return Some(Value::Object(fields));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.eat(":")?;
+            fields.push((key, self.value()?));
+
+            if self.eat("}").is_some() {
+                return Some(Value::Object(fields));
+            }
+            self.eat(",")?;
+        }
+    }
+}
+
+/// `None` if `input` is not exactly one JSON value
+pub fn parse(input: &str) -> Option<Value> {
+    let mut parser = Parser { input, pos: 0 };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+
+    parser.rest().is_empty().then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(value: &Value) -> Option<Value> {
+        parse(&value.to_string())
+    }
+
+    #[test]
+    fn written_values_parse_back() {
+        let value = Value::Object(vec![
+            ("null".into(), Value::Null),
+            (
+                "bools".into(),
+                Value::Array(vec![true.into(), false.into()]),
+            ),
+            (
+                "numbers".into(),
+                Value::Array(vec![0.0.into(), (-1.5).into(), 1e21.into()]),
+            ),
+            ("empty".into(), Value::Object(Vec::new())),
+            (
+                "nested".into(),
+                Value::Array(vec![Value::Array(Vec::new()), "x".into()]),
+            ),
+        ]);
+
+        assert_eq!(round_trip(&value), Some(value));
+    }
+
+    #[test]
+    fn escaped_strings_parse_back() {
+        let strings = [
+            "",
+            "quote \" and backslash \\",
+            "lines\nand\ttabs\r",
+            "control \u{1} \u{1f}",
+            "unicode é € 🦀",
+            "a \\\" mess \\n",
+        ];
+
+        for s in strings {
+            let value = Value::Object(vec![(s.into(), s.into())]);
+            assert_eq!(round_trip(&value), Some(value), "{s:?}");
+        }
+
+        assert_eq!(Value::from("a\"b\n").to_string(), r#""a\"b\n""#);
+        assert_eq!(Value::from("\u{1}").to_string(), r#""\u0001""#);
+    }
+
+    #[test]
+    fn json_from_elsewhere_parses() {
+        let value = parse(" { \"a\" : [ 1 , 2e3 , \"\\u00e9\\/\" ] , \"b\" : null } \n").unwrap();
+
+        assert_eq!(
+            value.get("a"),
+            Some(&Value::Array(vec![1.0.into(), 2000.0.into(), "é/".into()]))
+        );
+        assert_eq!(value.get("b"), Some(&Value::Null));
+        assert_eq!(value.get("c"), None);
+    }
+
+    #[test]
+    fn broken_json_is_rejected() {
+        for input in [
+            "",
+            "{",
+            "[1,]",
+            "{\"a\" 1}",
+            "\"open",
+            "1 2",
+            "nul",
+            "{1: 2}",
+        ] {
+            assert_eq!(parse(input), None, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn non_finite_numbers_are_written_as_null() {
+        assert_eq!(Value::from(f64::NAN).to_string(), "null");
+        assert_eq!(Value::from(f64::INFINITY).to_string(), "null");
+    }
+}
@@ -0,0 +1,18 @@
+//! shared code for the solutions of every year
+
+pub mod anim;
+pub mod args;
+pub mod bench;
+pub mod bitgrid;
+pub mod budget;
+pub mod cycle;
+pub mod diff;
+pub mod examples;
+pub mod generate;
+pub mod graph;
+pub mod image;
+pub mod json;
+pub mod log;
+pub mod prop;
+pub mod report;
+pub mod run;
@@ -0,0 +1,268 @@
+//! logging to stderr, so the answers on stdout stay clean.
+//!
+//! messages go through [`error!`](crate::error), [`warn!`](crate::warn), [`info!`](crate::info),
+//! [`debug!`](crate::debug) and [`trace!`](crate::trace), and their target is the day they're
+//! written in (e.g. `2023/day10`, or `aoc` for this crate). which ones are shown is set with
+//! `AOC_LOG`, a list of `target=level` or just `level` for everything else, e.g.
+//! ```text
+//! AOC_LOG=2023/day10=trace,info cargo run -p day10
+//! ```
+//! a target also covers the ones under it, so `2023=debug` is every day of 2023.
+//! without `AOC_LOG` only warnings and errors are shown. the arguments of a message that isn't
+//! shown aren't evaluated, so big dumps cost nothing when they're off
+
+use std::{
+    env, fmt,
+    io::{self, Write},
+    path::Path,
+    sync::OnceLock,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "error" => Some(Level::Error),
+            "warn" => Some(Level::Warn),
+            "info" => Some(Level::Info),
+            "debug" => Some(Level::Debug),
+            "trace" => Some(Level::Trace),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        })
+    }
+}
+
+/// the most detailed level shown for a target, `None` for every target without its own
+type Directive = (Option<String>, Level);
+
+/// ignores what it doesn't understand, rather than failing a run over its logs
+fn parse_directives(spec: &str) -> Vec<Directive> {
+    spec.split(',')
+        .filter_map(|directive| match directive.split_once('=') {
+            Some((target, level)) => Some((Some(target.trim().to_owned()), Level::parse(level)?)),
+            None => Some((None, Level::parse(directive)?)),
+        })
+        .collect()
+}
+
+/// `AOC_LOG`, parsed once
+struct Filter {
+    directives: Vec<Directive>,
+    /// the most detailed level of any target, to skip the rest quickly
+    max: Option<Level>,
+}
+
+fn filter() -> &'static Filter {
+    static FILTER: OnceLock<Filter> = OnceLock::new();
+
+    FILTER.get_or_init(|| {
+        let directives = match env::var("AOC_LOG") {
+            Ok(spec) => parse_directives(&spec),
+            Err(_) => vec![(None, Level::Warn)],
+        };
+        let max = directives.iter().map(|&(_, level)| level).max();
+
+        Filter { directives, max }
+    })
+}
+
+/// `2023/day10` for a day, from the folder of its crate, or the name of the folder otherwise
+pub fn target(manifest_dir: &str) -> String {
+    fn name(path: &Path) -> Option<&str> {
+        path.file_name()?.to_str()
+    }
+
+    let path = Path::new(manifest_dir);
+
+    match (path.parent().and_then(name), name(path)) {
+        (Some(year), Some(day)) if year.bytes().all(|b| b.is_ascii_digit()) => {
+            format!("{year}/{day}")
+        }
+        (_, Some(name)) => name.to_owned(),
+        _ => manifest_dir.to_owned(),
+    }
+}
+
+/// whether `target` is `directive` or under it
+fn covers(directive: &str, target: &str) -> bool {
+    target
+        .strip_prefix(directive)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// the most specific directive for the target wins, and an unmentioned target gets nothing
+fn enabled_in(directives: &[Directive], level: Level, target: &str) -> bool {
+    let mut best: Option<(usize, Level)> = None;
+
+    for (directive, max) in directives {
+        let specificity = match directive {
+            Some(directive) if covers(directive, target) => directive.len() + 1,
+            Some(_) => continue,
+            None => 0,
+        };
+
+        if best.is_none_or(|(best, _)| specificity >= best) {
+            best = Some((specificity, *max));
+        }
+    }
+
+    best.is_some_and(|(_, max)| level <= max)
+}
+
+/// called by the macros first: whether `level` is shown for any target at all
+pub fn shown(level: Level) -> bool {
+    filter().max.is_some_and(|max| level <= max)
+}
+
+/// called by the macros next, with the [`target`] of the caller
+pub fn enabled(level: Level, target: &str) -> bool {
+    enabled_in(&filter().directives, level, target)
+}
+
+/// called by the macros, which check `enabled` first
+pub fn write(level: Level, target: &str, args: fmt::Arguments) {
+    let message = args.to_string();
+
+    // a multi-line message, like a grid, starts on a line of its own
+    let separator = if message.contains('\n') { "\n" } else { " " };
+
+    let _ = writeln!(io::stderr(), "[{level:<5} {target}]{separator}{message}");
+}
+
+/// logs at a [`Level`](crate::log::Level), see the [`log`](crate::log) module
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {{
+        // the target of the messages from here, only worked out once
+        static TARGET: ::std::sync::OnceLock<String> = ::std::sync::OnceLock::new();
+        let level = $level;
+
+        if $crate::log::shown(level) {
+            let target = TARGET.get_or_init(|| $crate::log::target(env!("CARGO_MANIFEST_DIR")));
+
+            if $crate::log::enabled(level, target) {
+                $crate::log::write(level, target, format_args!($($arg)+));
+            }
+        }
+    }};
+}
+
+/// see the [`log`](crate::log) module
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Error, $($arg)+) };
+}
+
+/// see the [`log`](crate::log) module
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Warn, $($arg)+) };
+}
+
+/// see the [`log`](crate::log) module
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Info, $($arg)+) };
+}
+
+/// see the [`log`](crate::log) module
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Debug, $($arg)+) };
+}
+
+/// see the [`log`](crate::log) module
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Trace, $($arg)+) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directives() {
+        assert_eq!(
+            parse_directives("2023/day10=trace, info,aoc = Warn"),
+            vec![
+                (Some("2023/day10".to_owned()), Level::Trace),
+                (None, Level::Info),
+                (Some("aoc".to_owned()), Level::Warn),
+            ]
+        );
+        assert_eq!(parse_directives(""), vec![]);
+    }
+
+    #[test]
+    fn invalid_levels_are_ignored() {
+        assert_eq!(
+            parse_directives("2023/day10=loud,verbose,debug,2024="),
+            vec![(None, Level::Debug)]
+        );
+    }
+
+    #[test]
+    fn targets_of_days() {
+        assert_eq!(target("/home/me/aoc/2023/day10"), "2023/day10");
+        assert_eq!(target("/home/me/aoc/aoc"), "aoc");
+        assert_eq!(target("/home/me/aoc/run_year"), "run_year");
+        assert_eq!(target("/"), "/");
+    }
+
+    #[test]
+    fn a_target_covers_the_ones_under_it() {
+        assert!(covers("2023", "2023/day10"));
+        assert!(covers("2023/day10", "2023/day10"));
+        assert!(!covers("2023/day1", "2023/day10"));
+        assert!(!covers("2023/day10", "2023"));
+    }
+
+    #[test]
+    fn the_most_specific_target_wins() {
+        let directives = parse_directives("2023/day10=trace,2023=error,debug");
+
+        assert!(enabled_in(&directives, Level::Trace, "2023/day10"));
+        assert!(enabled_in(&directives, Level::Error, "2023/day1"));
+        assert!(!enabled_in(&directives, Level::Warn, "2023/day1"));
+        assert!(enabled_in(&directives, Level::Debug, "2024/day6"));
+        assert!(!enabled_in(&directives, Level::Trace, "2024/day6"));
+
+        // in any order
+        let directives = parse_directives("debug,2023=error,2023/day10=trace");
+        assert!(enabled_in(&directives, Level::Trace, "2023/day10"));
+        assert!(!enabled_in(&directives, Level::Warn, "2023/day1"));
+    }
+
+    #[test]
+    fn the_default_level_is_for_everything_else() {
+        let directives = parse_directives("info");
+        assert!(enabled_in(&directives, Level::Info, "2015/day7"));
+        assert!(!enabled_in(&directives, Level::Debug, "2015/day7"));
+
+        // without one, a target nobody mentioned shows nothing
+        let directives = parse_directives("2023=trace");
+        assert!(!enabled_in(&directives, Level::Error, "2015/day7"));
+        assert!(!enabled_in(&[], Level::Error, "2015/day7"));
+    }
+}
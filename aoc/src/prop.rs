@@ -0,0 +1,48 @@
+//! property-based testing, small and seeded: a failure says which seed to replay.
+//!
+//! for parsers, the usual properties are that what's printed parses back to the same value,
+//! and that a [`corrupt`] input is rejected, or read as a value that's printed back as that
+//! input (a deleted digit can make another valid one), rather than skipped over or guessed at
+
+use std::fmt::Debug;
+
+use crate::generate::Rng;
+
+/// how many values `check` tries
+pub const CASES: u64 = 256;
+
+/// characters that none of the parsers tested with [`corrupt`] accept (unlike `#`, a wall in
+/// some grids), one of them takes more than a byte
+const NOISE: [char; 4] = ['?', '~', '@', 'é'];
+
+/// panics with the first value made by `generate` that `property` doesn't hold for
+pub fn check<T: Debug>(
+    mut generate: impl FnMut(&mut Rng) -> T,
+    mut property: impl FnMut(&T) -> bool,
+) {
+    for seed in 0..CASES {
+        let value = generate(&mut Rng::new(seed));
+
+        assert!(property(&value), "doesn't hold for seed {seed}: {value:?}");
+    }
+}
+
+/// `s` with one character replaced by noise, noise inserted somewhere (the end included), or one
+/// character deleted or doubled
+pub fn corrupt(rng: &mut Rng, s: &str) -> String {
+    let mut chars = s.chars().collect::<Vec<_>>();
+    let i = rng.below(chars.len() as u64 + 1) as usize;
+    let noise = *rng.choose(&NOISE);
+
+    match rng.below(4) {
+        _ if i == chars.len() => chars.insert(i, noise),
+        0 => chars[i] = noise,
+        1 => chars.insert(i, noise),
+        2 => {
+            chars.remove(i);
+        }
+        _ => chars.insert(i, chars[i]),
+    }
+
+    chars.into_iter().collect()
+}
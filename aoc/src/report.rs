@@ -0,0 +1,212 @@
+//! the answers of a day, printed as they are (`None` without one), or with `--json` as one object
+//! per line for the tools that read them, e.g.
+//! ```text
+//! {"year":2015,"day":6,"part":1,"input":"input1","answer":"377891","type":"u32","status":"ok","parse_ns":1520300,"solve_ns":18374913}
+//! ```
+//! - `answer` is a string, so that big numbers survive, and `null` without an answer
+//! - `status` is `ok`, `none` if the part returned `None`, or `panicked`
+//! - the parts parse their input themselves: if the day times its parser with [`Report::parse`],
+//!   that's `parse_ns` and it's taken out of `solve_ns`, otherwise `parse_ns` is `null`
+
+use std::{
+    env,
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+use crate::json::Value;
+
+/// what a part can return
+pub trait Answer {
+    /// the name of the type of the answer
+    const TYPE: &'static str;
+
+    /// `None` if the part didn't find an answer
+    fn value(&self) -> Option<String>;
+}
+
+macro_rules! answer {
+    ($($t:ty),*) => {
+        $(
+            impl Answer for $t {
+                const TYPE: &'static str = stringify!($t);
+
+                fn value(&self) -> Option<String> {
+                    Some(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+answer!(i32, i64, u16, u32, u64, usize, String);
+
+impl<T: Answer> Answer for Option<T> {
+    const TYPE: &'static str = T::TYPE;
+
+    fn value(&self) -> Option<String> {
+        self.as_ref()?.value()
+    }
+}
+
+/// the answers of a day, named like in the benchmarks (e.g. `2015/day6`)
+pub struct Report {
+    year: u32,
+    day: u32,
+    json: bool,
+    /// how long parsing took, for every input
+    parsed: Vec<(String, Duration)>,
+}
+
+impl Report {
+    pub fn from_args(name: &str) -> Self {
+        let (year, day) = name.split_once("/day").expect("a name like 2015/day6");
+
+        Report {
+            year: year.parse().expect("a name like 2015/day6"),
+            day: day.parse().expect("a name like 2015/day6"),
+            json: env::args().any(|arg| arg == "--json"),
+            parsed: Vec::new(),
+        }
+    }
+
+    /// times the parser of the day on `input`, only for the JSON output
+    pub fn parse<T>(&mut self, input: &str, parse: impl FnOnce() -> T) -> &mut Self {
+        if self.json {
+            let started = Instant::now();
+            parse();
+            self.parsed.push((input.to_owned(), started.elapsed()));
+        }
+
+        self
+    }
+
+    /// prints the answer of `part` for `input` (the name of the file, e.g. `input1`)
+    pub fn part<T: Answer>(
+        &mut self,
+        part: u32,
+        input: &str,
+        solve: impl FnOnce() -> T,
+    ) -> &mut Self {
+        if !self.json {
+            println!("{}", solve().value().as_deref().unwrap_or("None"));
+            return self;
+        }
+
+        let started = Instant::now();
+        let answer = panic::catch_unwind(AssertUnwindSafe(solve));
+        let elapsed = started.elapsed();
+
+        println!("{}", self.line(part, input, answer.as_ref().ok(), elapsed));
+
+        self
+    }
+
+    /// the JSON line of `part` for `input`, whose answer is `None` if it panicked
+    fn line<T: Answer>(
+        &self,
+        part: u32,
+        input: &str,
+        answer: Option<&T>,
+        elapsed: Duration,
+    ) -> Value {
+        let value = answer.and_then(Answer::value);
+        let status = match answer {
+            Some(_) if value.is_some() => "ok",
+            Some(_) => "none",
+            None => "panicked",
+        };
+
+        let parse = self
+            .parsed
+            .iter()
+            .find(|(name, _)| name == input)
+            .map(|&(_, parse)| parse);
+        let solve = elapsed.saturating_sub(parse.unwrap_or_default());
+        let nanos = |duration: Duration| duration.as_nanos() as f64;
+
+        Value::Object(vec![
+            ("year".into(), (self.year as f64).into()),
+            ("day".into(), (self.day as f64).into()),
+            ("part".into(), (part as f64).into()),
+            ("input".into(), input.into()),
+            ("answer".into(), value.as_deref().into()),
+            ("type".into(), T::TYPE.into()),
+            ("status".into(), status.into()),
+            ("parse_ns".into(), parse.map(nanos).into()),
+            ("solve_ns".into(), nanos(solve).into()),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json;
+
+    fn report(parsed: &[(&str, u64)]) -> Report {
+        Report {
+            year: 2015,
+            day: 6,
+            json: true,
+            parsed: parsed
+                .iter()
+                .map(|&(input, nanos)| (input.to_owned(), Duration::from_nanos(nanos)))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn an_answer() {
+        let line = report(&[]).line(1, "input1", Some(&377891u32), Duration::from_nanos(1500));
+
+        assert_eq!(
+            line.to_string(),
+            r#"{"year":2015,"day":6,"part":1,"input":"input1","answer":"377891","type":"u32","status":"ok","parse_ns":null,"solve_ns":1500}"#
+        );
+    }
+
+    #[test]
+    fn parsing_is_taken_out_of_solving() {
+        let report = report(&[("input0", 200), ("input1", 400)]);
+        let line = report.line(2, "input1", Some(&Some(7u64)), Duration::from_nanos(1000));
+
+        assert_eq!(line.get("parse_ns").and_then(Value::as_f64), Some(400.0));
+        assert_eq!(line.get("solve_ns").and_then(Value::as_f64), Some(600.0));
+        assert_eq!(line.get("type").and_then(Value::as_str), Some("u64"));
+    }
+
+    #[test]
+    fn without_an_answer() {
+        let report = report(&[]);
+
+        let none = report.line(1, "input0", Some(&None::<u32>), Duration::ZERO);
+        assert_eq!(none.get("answer"), Some(&Value::Null));
+        assert_eq!(none.get("status").and_then(Value::as_str), Some("none"));
+
+        let panicked = report.line::<String>(1, "input0", None, Duration::ZERO);
+        assert_eq!(panicked.get("answer"), Some(&Value::Null));
+        assert_eq!(
+            panicked.get("status").and_then(Value::as_str),
+            Some("panicked")
+        );
+    }
+
+    #[test]
+    fn big_answers_are_strings() {
+        let line = report(&[]).line(1, "input1", Some(&u64::MAX), Duration::ZERO);
+        let line = json::parse(&line.to_string()).unwrap();
+
+        assert_eq!(
+            line.get("answer").and_then(Value::as_str),
+            Some(u64::MAX.to_string().as_str())
+        );
+    }
+
+    #[test]
+    fn answers_are_plain() {
+        assert_eq!(Some(42u32).value().as_deref(), Some("42"));
+        assert_eq!(None::<u32>.value(), None);
+        assert_eq!(String::from("abc").value().as_deref(), Some("abc"));
+    }
+}
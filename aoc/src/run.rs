@@ -0,0 +1,153 @@
+//! the `main` of a day: its inputs, parsers and parts, run the way the flags say.
+//! - with `--bench`, the parsers and the parts are benchmarked on the puzzle input, see
+//!   [`bench`](crate::bench)
+//! - with `--budget SECS`, every part gets a time budget on the puzzle input, see
+//!   [`budget`](crate::budget)
+//! - otherwise the answers of every part for every input are printed, see
+//!   [`report`](crate::report)
+//!
+//! a day looks for its own flags (`--images`, ...) first, then e.g.
+//! ```text
+//! aoc::run::Day::new("2015/day6")
+//!     .input("input0", input0)
+//!     .input("input1", input1)
+//!     .parse("parse", parse_input)
+//!     .part(1, part1)
+//!     .part(2, part2)
+//!     .run();
+//! ```
+
+use std::fmt::Debug;
+
+use crate::{
+    bench::Bench,
+    budget::Budget,
+    report::{Answer, Report},
+};
+
+/// what the flags asked for
+enum Mode {
+    Bench(Bench),
+    Budget(Budget),
+    Report(Report),
+}
+
+/// a parser or a part, run on an input (its name, then itself) the way the mode says.
+/// a closure, so that the type of what it returns is known where it's run
+type Step<'a> = Box<dyn Fn(&mut Mode, &'a str, &'a str) + 'a>;
+
+/// a day, named like in the benchmarks (e.g. `2015/day6`)
+pub struct Day<'a> {
+    name: &'a str,
+    /// with their name, and the part they're only for, if any
+    inputs: Vec<(&'a str, &'a str, Option<u32>)>,
+    parsers: Vec<Step<'a>>,
+    parts: Vec<(u32, Step<'a>)>,
+}
+
+impl<'a> Day<'a> {
+    pub fn new(name: &'a str) -> Self {
+        Day {
+            name,
+            inputs: Vec::new(),
+            parsers: Vec::new(),
+            parts: Vec::new(),
+        }
+    }
+
+    /// an input for every part, e.g. `input0`. the last one is the puzzle input, the one that's
+    /// benchmarked and budgeted
+    pub fn input(mut self, name: &'a str, input: &'a str) -> Self {
+        self.inputs.push((name, input, None));
+        self
+    }
+
+    /// an input only for `part`, like an example that only fits one of them
+    pub fn input_for(mut self, part: u32, name: &'a str, input: &'a str) -> Self {
+        self.inputs.push((name, input, Some(part)));
+        self
+    }
+
+    /// only benchmarked, unless it's labelled `parse`: then the report also times it, and takes
+    /// that out of the time of the parts
+    pub fn parse<T>(mut self, label: &'a str, parse: impl Fn(&'a str) -> T + 'a) -> Self {
+        self.parsers
+            .push(Box::new(move |mode, name, input| match mode {
+                Mode::Bench(bench) => {
+                    bench.run(label, || parse(input));
+                }
+                Mode::Report(report) if label == "parse" => {
+                    report.parse(name, || parse(input));
+                }
+                Mode::Budget(_) | Mode::Report(_) => (),
+            }));
+        self
+    }
+
+    /// `part` is 1 or 2, labelled `part1` or `part2` in the benchmarks and budgets
+    pub fn part<T: Answer + Debug>(mut self, part: u32, solve: impl Fn(&'a str) -> T + 'a) -> Self {
+        let label = format!("part{part}");
+
+        self.parts.push((
+            part,
+            Box::new(move |mode, name, input| match mode {
+                Mode::Bench(bench) => {
+                    bench.run(&label, || solve(input));
+                }
+                Mode::Budget(budget) => {
+                    budget.run(&label, || solve(input));
+                }
+                Mode::Report(report) => {
+                    report.part(part, name, || solve(input));
+                }
+            }),
+        ));
+        self
+    }
+
+    /// exits with an error status if a part went over its budget
+    pub fn run(self) {
+        let (name, puzzle, _) = *self
+            .inputs
+            .iter()
+            .rfind(|(_, _, only)| only.is_none())
+            .expect("a day without an input");
+        let parts = || self.parts.iter().map(|(_, part)| part);
+
+        if let Some(bench) = Bench::from_args(self.name) {
+            let mut mode = Mode::Bench(bench);
+            for step in self.parsers.iter().chain(parts()) {
+                step(&mut mode, name, puzzle);
+            }
+            if let Mode::Bench(bench) = mode {
+                bench.finish();
+            }
+            return;
+        }
+
+        if let Some(budget) = Budget::from_args(self.name) {
+            let mut mode = Mode::Budget(budget);
+            for part in parts() {
+                part(&mut mode, name, puzzle);
+            }
+            if let Mode::Budget(budget) = mode {
+                budget.finish();
+            }
+            return;
+        }
+
+        let mut mode = Mode::Report(Report::from_args(self.name));
+        for &(name, input, _) in &self.inputs {
+            for parse in &self.parsers {
+                parse(&mut mode, name, input);
+            }
+        }
+        for (part, solve) in &self.parts {
+            for &(name, input, only) in &self.inputs {
+                if only.is_none_or(|only| only == *part) {
+                    solve(&mut mode, name, input);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! compares scalar `md5` with `md5_lanes`, on short messages like the ones of a nonce search.
+//! run with `cargo run --release --example lanes`
+
+use std::hint::black_box;
+use std::time::Instant;
+
+const MESSAGES: usize = 1 << 20;
+
+fn bench(desc: &str, workload: impl FnOnce()) {
+    let before = Instant::now();
+    workload();
+    println!("{desc}: {:.2?}", before.elapsed());
+}
+
+fn run_lanes<const N: usize>(messages: &[Vec<u8>]) -> Vec<md5::Digest> {
+    messages
+        .chunks_exact(N)
+        .flat_map(|chunk| md5::md5_lanes::<N>(std::array::from_fn(|l| chunk[l].as_slice())))
+        .collect()
+}
+
+fn main() {
+    let messages = (0..MESSAGES)
+        .map(|n| format!("yzbqklnj{}", 1_000_000 + n).into_bytes())
+        .collect::<Vec<_>>();
+
+    let mut scalar = Vec::new();
+    bench("scalar", || {
+        scalar = messages.iter().map(md5::md5).collect();
+    });
+
+    let mut lanes4 = Vec::new();
+    bench("4 lanes", || lanes4 = black_box(run_lanes::<4>(&messages)));
+
+    let mut lanes8 = Vec::new();
+    bench("8 lanes", || lanes8 = black_box(run_lanes::<8>(&messages)));
+
+    let mut lanes16 = Vec::new();
+    bench("16 lanes (portable)", || {
+        lanes16 = black_box(run_lanes::<16>(&messages))
+    });
+
+    assert_eq!(scalar, lanes4);
+    assert_eq!(scalar, lanes8);
+    assert_eq!(scalar, lanes16);
+}
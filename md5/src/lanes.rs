@@ -0,0 +1,372 @@
+//! the same rounds as the scalar code, run on several independent messages at once.
+//! on x86_64 4 lanes use SSE2 and 8 lanes use AVX2 (when the cpu has it), any other
+//! combination runs on plain arrays the compiler is free to vectorize
+
+use crate::{block_words, state_bytes, Digest, Hasher, Round, BLOCK_SIZE, LENGTH_OFFSET};
+
+/// the operations the rounds need, on one 32-bit word per lane
+trait Vector: Copy {
+    fn splat(value: u32) -> Self;
+    fn from_lanes(values: &[u32]) -> Self;
+    fn to_lanes(self, out: &mut [u32]);
+    fn add(self, other: Self) -> Self;
+    fn and(self, other: Self) -> Self;
+    fn or(self, other: Self) -> Self;
+    fn xor(self, other: Self) -> Self;
+    fn not(self) -> Self;
+    fn rotate_left(self, shift: u32) -> Self;
+}
+
+impl<const N: usize> Vector for [u32; N] {
+    #[inline(always)]
+    fn splat(value: u32) -> Self {
+        [value; N]
+    }
+
+    #[inline(always)]
+    fn from_lanes(values: &[u32]) -> Self {
+        values.try_into().unwrap()
+    }
+
+    #[inline(always)]
+    fn to_lanes(self, out: &mut [u32]) {
+        out.copy_from_slice(&self);
+    }
+
+    #[inline(always)]
+    fn add(self, other: Self) -> Self {
+        std::array::from_fn(|l| self[l].wrapping_add(other[l]))
+    }
+
+    #[inline(always)]
+    fn and(self, other: Self) -> Self {
+        std::array::from_fn(|l| self[l] & other[l])
+    }
+
+    #[inline(always)]
+    fn or(self, other: Self) -> Self {
+        std::array::from_fn(|l| self[l] | other[l])
+    }
+
+    #[inline(always)]
+    fn xor(self, other: Self) -> Self {
+        std::array::from_fn(|l| self[l] ^ other[l])
+    }
+
+    #[inline(always)]
+    fn not(self) -> Self {
+        self.map(|v| !v)
+    }
+
+    #[inline(always)]
+    fn rotate_left(self, shift: u32) -> Self {
+        self.map(|v| v.rotate_left(shift))
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use std::arch::x86_64::*;
+
+    use super::Vector;
+
+    impl Vector for __m128i {
+        #[inline(always)]
+        fn splat(value: u32) -> Self {
+            unsafe { _mm_set1_epi32(value as i32) }
+        }
+
+        #[inline(always)]
+        fn from_lanes(values: &[u32]) -> Self {
+            assert_eq!(values.len(), 4);
+            // SAFETY: 4 lanes of 32 bits are exactly 128 bits, and `loadu` has no alignment requirement
+            unsafe { _mm_loadu_si128(values.as_ptr().cast()) }
+        }
+
+        #[inline(always)]
+        fn to_lanes(self, out: &mut [u32]) {
+            assert_eq!(out.len(), 4);
+            // SAFETY: same as in `from_lanes`
+            unsafe { _mm_storeu_si128(out.as_mut_ptr().cast(), self) }
+        }
+
+        #[inline(always)]
+        fn add(self, other: Self) -> Self {
+            unsafe { _mm_add_epi32(self, other) }
+        }
+
+        #[inline(always)]
+        fn and(self, other: Self) -> Self {
+            unsafe { _mm_and_si128(self, other) }
+        }
+
+        #[inline(always)]
+        fn or(self, other: Self) -> Self {
+            unsafe { _mm_or_si128(self, other) }
+        }
+
+        #[inline(always)]
+        fn xor(self, other: Self) -> Self {
+            unsafe { _mm_xor_si128(self, other) }
+        }
+
+        #[inline(always)]
+        fn not(self) -> Self {
+            self.xor(Self::splat(u32::MAX))
+        }
+
+        #[inline(always)]
+        fn rotate_left(self, shift: u32) -> Self {
+            unsafe {
+                let left = _mm_sll_epi32(self, _mm_cvtsi32_si128(shift as i32));
+                let right = _mm_srl_epi32(self, _mm_cvtsi32_si128(32 - shift as i32));
+                _mm_or_si128(left, right)
+            }
+        }
+    }
+
+    impl Vector for __m256i {
+        #[inline(always)]
+        fn splat(value: u32) -> Self {
+            unsafe { _mm256_set1_epi32(value as i32) }
+        }
+
+        #[inline(always)]
+        fn from_lanes(values: &[u32]) -> Self {
+            assert_eq!(values.len(), 8);
+            // SAFETY: 8 lanes of 32 bits are exactly 256 bits, and `loadu` has no alignment requirement
+            unsafe { _mm256_loadu_si256(values.as_ptr().cast()) }
+        }
+
+        #[inline(always)]
+        fn to_lanes(self, out: &mut [u32]) {
+            assert_eq!(out.len(), 8);
+            // SAFETY: same as in `from_lanes`
+            unsafe { _mm256_storeu_si256(out.as_mut_ptr().cast(), self) }
+        }
+
+        #[inline(always)]
+        fn add(self, other: Self) -> Self {
+            unsafe { _mm256_add_epi32(self, other) }
+        }
+
+        #[inline(always)]
+        fn and(self, other: Self) -> Self {
+            unsafe { _mm256_and_si256(self, other) }
+        }
+
+        #[inline(always)]
+        fn or(self, other: Self) -> Self {
+            unsafe { _mm256_or_si256(self, other) }
+        }
+
+        #[inline(always)]
+        fn xor(self, other: Self) -> Self {
+            unsafe { _mm256_xor_si256(self, other) }
+        }
+
+        #[inline(always)]
+        fn not(self) -> Self {
+            self.xor(Self::splat(u32::MAX))
+        }
+
+        #[inline(always)]
+        fn rotate_left(self, shift: u32) -> Self {
+            unsafe {
+                let left = _mm256_sll_epi32(self, _mm_cvtsi32_si128(shift as i32));
+                let right = _mm256_srl_epi32(self, _mm_cvtsi32_si128(32 - shift as i32));
+                _mm256_or_si256(left, right)
+            }
+        }
+    }
+
+    /// SSE2 is part of the x86_64 baseline, so this needs no detection
+    pub fn digest_blocks_sse2(blocks: &[[u32; 16]; 4], state: &mut [[u32; 4]; 4]) {
+        super::digest_blocks::<__m128i, 4>(blocks, state)
+    }
+
+    /// # Safety
+    /// the cpu must support AVX2
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn digest_blocks_avx2(blocks: &[[u32; 16]; 8], state: &mut [[u32; 4]; 8]) {
+        super::digest_blocks::<__m256i, 8>(blocks, state)
+    }
+}
+
+impl Round {
+    #[inline(always)]
+    fn fx_lanes<V: Vector>(&self, bb: V, cc: V, dd: V) -> V {
+        match self.0 {
+            0..16 => bb.and(cc).or(bb.not().and(dd)),
+            16..32 => dd.and(bb).or(dd.not().and(cc)),
+            32..48 => bb.xor(cc).xor(dd),
+            48..64 => cc.xor(bb.or(dd.not())),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// `digest_block` for one block per lane
+#[inline(always)]
+fn digest_blocks<V: Vector, const N: usize>(blocks: &[[u32; 16]; N], state: &mut [[u32; 4]; N]) {
+    let words: [V; 16] = std::array::from_fn(|i| {
+        let lanes: [u32; N] = std::array::from_fn(|l| blocks[l][i]);
+        V::from_lanes(&lanes)
+    });
+    let initial: [V; 4] = std::array::from_fn(|i| {
+        let lanes: [u32; N] = std::array::from_fn(|l| state[l][i]);
+        V::from_lanes(&lanes)
+    });
+    let [mut aa, mut bb, mut cc, mut dd] = initial;
+
+    for round in Round::rounds() {
+        let tmp = aa
+            .add(round.fx_lanes(bb, cc, dd))
+            .add(words[round.index()])
+            .add(V::splat(round.k()))
+            .rotate_left(round.shift())
+            .add(bb);
+
+        (aa, bb, cc, dd) = (dd, tmp, bb, cc);
+    }
+
+    let mut lanes = [0u32; N];
+    for (i, word) in [aa, bb, cc, dd].into_iter().enumerate() {
+        word.add(initial[i]).to_lanes(&mut lanes);
+        state.iter_mut().zip(lanes).for_each(|(s, v)| s[i] = v);
+    }
+}
+
+fn digest_lanes<const N: usize>(blocks: &[[u32; 16]; N], state: &mut [[u32; 4]; N]) {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if N == 4 {
+            let blocks = blocks.as_slice().try_into().unwrap();
+            let state = state.as_mut_slice().try_into().unwrap();
+            return x86::digest_blocks_sse2(blocks, state);
+        }
+
+        if N == 8 && is_x86_feature_detected!("avx2") {
+            let blocks = blocks.as_slice().try_into().unwrap();
+            let state = state.as_mut_slice().try_into().unwrap();
+            // SAFETY: just checked the cpu supports AVX2
+            return unsafe { x86::digest_blocks_avx2(blocks, state) };
+        }
+    }
+
+    digest_blocks::<[u32; N], N>(blocks, state)
+}
+
+impl Hasher {
+    /// how many blocks are left to digest if `suffix` is the end of the message
+    fn blocks_left(&self, suffix: &[u8]) -> usize {
+        // the 0x80 byte and the 8 bytes of the length
+        (self.buffered + suffix.len() + 9).div_ceil(BLOCK_SIZE)
+    }
+
+    /// block `index` of what's left of the message, once `suffix` and the padding are added
+    fn tail_block(&self, suffix: &[u8], index: usize, blocks_left: usize) -> [u8; BLOCK_SIZE] {
+        let mut block = [0; BLOCK_SIZE];
+        let start = index * BLOCK_SIZE;
+        let end = start + BLOCK_SIZE;
+
+        let mut copy = |from: usize, data: &[u8]| {
+            let lo = from.clamp(start, end);
+            let hi = (from + data.len()).clamp(start, end);
+
+            if lo < hi {
+                block[lo - start..hi - start].copy_from_slice(&data[lo - from..hi - from]);
+            }
+        };
+
+        let message_end = self.buffered + suffix.len();
+        copy(0, &self.buffer[..self.buffered]);
+        copy(self.buffered, suffix);
+        copy(message_end, &[0x80]);
+
+        if index + 1 == blocks_left {
+            let input_len = (self.len.wrapping_add(suffix.len() as u64)).wrapping_mul(8);
+            block[LENGTH_OFFSET..].copy_from_slice(&input_len.to_le_bytes());
+        }
+
+        block
+    }
+
+    /// the digests of `N` messages, each made of what was given to `self` so far followed by
+    /// one of `suffixes`. same result as cloning `self` and finalizing each, but all lanes go
+    /// through the rounds together.
+    /// lanes need the same number of blocks, otherwise they get hashed one by one
+    pub fn finalize_lanes<const N: usize>(&self, suffixes: [&[u8]; N]) -> [Digest; N] {
+        let blocks_left = self.blocks_left(suffixes[0]);
+
+        if suffixes.iter().any(|s| self.blocks_left(s) != blocks_left) {
+            return suffixes.map(|suffix| {
+                let mut hasher = self.clone();
+                hasher.update(suffix);
+                hasher.finalize()
+            });
+        }
+
+        let mut state = [self.state; N];
+
+        for index in 0..blocks_left {
+            let blocks = suffixes.map(|s| block_words(&self.tail_block(s, index, blocks_left)));
+            digest_lanes(&blocks, &mut state);
+        }
+
+        state.map(|s| state_bytes(&s))
+    }
+}
+
+/// `md5` of every message in `messages`, `N` at a time
+pub fn md5_lanes<const N: usize>(messages: [&[u8]; N]) -> [Digest; N] {
+    Hasher::new().finalize_lanes(messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::md5;
+
+    fn check_lanes<const N: usize>() {
+        let data = (0..=255u8).cycle().take(300).collect::<Vec<_>>();
+
+        for len in [0, 1, 3, 54, 55, 56, 57, 63, 64, 65, 119, 120, 128, 200] {
+            // same block count, different content in every lane
+            let messages: [Vec<u8>; N] = std::array::from_fn(|l| data[l..l + len].to_vec());
+            let expected = messages.clone().map(md5);
+
+            let mut prefix = Hasher::new();
+            prefix.update(&data[..len / 2]);
+            let expected_with_prefix = messages.clone().map(|m| {
+                let mut hasher = prefix.clone();
+                hasher.update(&m);
+                hasher.finalize()
+            });
+
+            let messages = messages.each_ref().map(|m| m.as_slice());
+
+            assert_eq!(md5_lanes(messages), expected, "{N} lanes of {len} bytes");
+            assert_eq!(
+                prefix.finalize_lanes(messages),
+                expected_with_prefix,
+                "{N} lanes of {len} bytes after a prefix"
+            );
+        }
+    }
+
+    #[test]
+    fn lanes_match_scalar() {
+        check_lanes::<1>();
+        check_lanes::<4>();
+        check_lanes::<8>();
+        check_lanes::<16>();
+    }
+
+    #[test]
+    fn lanes_with_different_block_counts() {
+        let messages: [&[u8]; 4] = [b"", b"abc", &[b'a'; 55], &[b'a'; 56]];
+
+        assert_eq!(md5_lanes(messages), messages.map(md5));
+    }
+}
@@ -0,0 +1,328 @@
+//! MD5 (RFC 1321), with a one-shot `md5` function and an incremental `Hasher`.
+//! `lanes` hashes several messages at once, for brute-force searches
+
+mod lanes;
+
+use std::fmt;
+
+pub use lanes::md5_lanes;
+
+const BLOCK_SIZE: usize = 64;
+
+/// where the message length goes in the last block
+const LENGTH_OFFSET: usize = BLOCK_SIZE - 8;
+
+const INITIAL_STATE: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+
+struct Round(u8);
+
+impl Round {
+    const NUM_ROUNDS: u8 = 64;
+
+    #[rustfmt::skip]
+    const SHIFT: [u8; Self::NUM_ROUNDS as usize] = [
+        7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22,
+        5, 9 , 14, 20, 5, 9 , 14, 20, 5, 9 , 14, 20, 5, 9 , 14, 20,
+        4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23,
+        6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+    ];
+
+    #[rustfmt::skip]
+    const K: [u32; Self::NUM_ROUNDS as usize] = [
+        0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee,
+        0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+        0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be,
+        0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+        0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa,
+        0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+        0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed,
+        0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+        0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c,
+        0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+        0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05,
+        0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+        0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039,
+        0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+        0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1,
+        0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391
+    ];
+
+    fn rounds() -> impl Iterator<Item = Round> {
+        (0..Self::NUM_ROUNDS).map(Round)
+    }
+
+    fn fx(&self, bb: u32, cc: u32, dd: u32) -> u32 {
+        match self.0 {
+            0..16 => (bb & cc) | ((!bb) & dd),
+            16..32 => (dd & bb) | ((!dd) & cc),
+            32..48 => bb ^ cc ^ dd,
+            48..64 => cc ^ (bb | (!dd)),
+            _ => unreachable!(),
+        }
+    }
+
+    fn k(&self) -> u32 {
+        Self::K[self.0 as usize]
+    }
+
+    fn shift(&self) -> u32 {
+        Self::SHIFT[self.0 as usize] as u32
+    }
+
+    /// which word of the block this round reads
+    fn index(&self) -> usize {
+        let index = match self.0 {
+            0..16 => self.0 as u32,
+            16..32 => (self.0 as u32 * 5 + 1) % 16,
+            32..48 => (self.0 as u32 * 3 + 5) % 16,
+            48..64 => (self.0 as u32 * 7) % 16,
+            _ => unreachable!(),
+        };
+
+        index as usize
+    }
+
+    fn input(&self, block: &[u32; 16]) -> u32 {
+        block[self.index()]
+    }
+
+    fn transform(&self, block: &[u32; 16], state: &mut [u32; 4]) {
+        let (aa, bb, cc, dd) = (state[0], state[1], state[2], state[3]);
+
+        let mut tmp = aa
+            .wrapping_add(self.fx(bb, cc, dd))
+            .wrapping_add(self.input(block))
+            .wrapping_add(self.k());
+        tmp = tmp.rotate_left(self.shift());
+        tmp = tmp.wrapping_add(bb);
+
+        *state = [dd, tmp, bb, cc];
+    }
+}
+
+fn block_words(input: &[u8; BLOCK_SIZE]) -> [u32; 16] {
+    let mut block = [0u32; 16];
+
+    input
+        .chunks_exact(4)
+        .zip(&mut block)
+        .for_each(|(chunk, b)| *b = u32::from_le_bytes(chunk.try_into().unwrap()));
+
+    block
+}
+
+fn state_bytes(state: &[u32; 4]) -> Digest {
+    let mut digest = [0; 16];
+
+    digest
+        .iter_mut()
+        .zip(state.iter().flat_map(|&word| word.to_le_bytes()))
+        .for_each(|(out, byte)| *out = byte);
+
+    Digest(digest)
+}
+
+fn digest_block(input: &[u8; BLOCK_SIZE], digest: &mut [u32; 4]) {
+    let block = block_words(input);
+    let mut state = *digest;
+
+    for round in Round::rounds() {
+        round.transform(&block, &mut state);
+    }
+
+    digest.iter_mut().zip(&state).for_each(|(d, &s)| {
+        *d = d.wrapping_add(s);
+    });
+}
+
+/// the 16 bytes of an MD5 hash. formats as hex with `{:x}`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Digest(pub [u8; 16]);
+
+impl Digest {
+    /// how many hex digits the digest starts with before a non-zero one
+    pub fn leading_zero_nibbles(&self) -> u32 {
+        let mut count = 0;
+
+        for byte in self.0 {
+            if byte == 0 {
+                count += 2;
+            } else {
+                if byte < 0x10 {
+                    count += 1;
+                }
+                break;
+            }
+        }
+
+        count
+    }
+}
+
+impl fmt::LowerHex for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for v in self.0 {
+            write!(f, "{v:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// incremental MD5: feed data with `update` as many times as needed, then `finalize`.
+/// cloning a `Hasher` keeps everything digested so far, so a common prefix only needs hashing once
+#[derive(Debug, Clone)]
+pub struct Hasher {
+    state: [u32; 4],
+    buffer: [u8; BLOCK_SIZE],
+    buffered: usize,
+    len: u64,
+}
+
+impl Default for Hasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher {
+    pub fn new() -> Self {
+        Hasher {
+            state: INITIAL_STATE,
+            buffer: [0; BLOCK_SIZE],
+            buffered: 0,
+            len: 0,
+        }
+    }
+
+    pub fn update<T: AsRef<[u8]>>(&mut self, data: T) {
+        let mut input = data.as_ref();
+        self.len = self.len.wrapping_add(input.len() as u64);
+
+        // top up a partially filled block first
+        if self.buffered > 0 {
+            let take = input.len().min(BLOCK_SIZE - self.buffered);
+            self.buffer[self.buffered..self.buffered + take].copy_from_slice(&input[..take]);
+            self.buffered += take;
+            input = &input[take..];
+
+            if self.buffered < BLOCK_SIZE {
+                return;
+            }
+
+            digest_block(&self.buffer, &mut self.state);
+            self.buffered = 0;
+        }
+
+        // digest message in 64 byte blocks
+        let mut blocks = input.chunks_exact(BLOCK_SIZE);
+        for block in blocks.by_ref() {
+            digest_block(block.try_into().unwrap(), &mut self.state);
+        }
+
+        let remaining = blocks.remainder();
+        self.buffer[..remaining.len()].copy_from_slice(remaining);
+        self.buffered = remaining.len();
+    }
+
+    pub fn finalize(mut self) -> Digest {
+        let input_len = self.len.wrapping_mul(8);
+
+        self.buffer[self.buffered] = 0x80;
+        self.buffer[self.buffered + 1..].fill(0);
+
+        // the length doesn't fit after the padding, so it goes in a block of its own
+        if self.buffered >= LENGTH_OFFSET {
+            digest_block(&self.buffer, &mut self.state);
+            self.buffer = [0; BLOCK_SIZE];
+        }
+
+        self.buffer[LENGTH_OFFSET..].copy_from_slice(&input_len.to_le_bytes());
+        digest_block(&self.buffer, &mut self.state);
+
+        state_bytes(&self.state)
+    }
+}
+
+/// hash `data` in one go
+pub fn md5<T: AsRef<[u8]>>(data: T) -> Digest {
+    let mut hasher = Hasher::new();
+    hasher.update(data);
+    hasher.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(digest: Digest) -> String {
+        format!("{digest:x}")
+    }
+
+    #[test]
+    fn rfc1321_test_suite() {
+        let vectors = [
+            ("", "d41d8cd98f00b204e9800998ecf8427e"),
+            ("a", "0cc175b9c0f1b6a831c399e269772661"),
+            ("abc", "900150983cd24fb0d6963f7d28e17f72"),
+            ("message digest", "f96b697d7cb7938d525a2f31aaf161d0"),
+            (
+                "abcdefghijklmnopqrstuvwxyz",
+                "c3fcd3d76192e4007dfb496cca67e13b",
+            ),
+            (
+                "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+                "d174ab98d277d9f5a5611c2c9f419d9f",
+            ),
+            (
+                "12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "57edf4a22be3c955ac49da2e2107b67a",
+            ),
+        ];
+
+        for (input, expected) in vectors {
+            assert_eq!(hex(md5(input)), expected, "md5({input:?})");
+        }
+    }
+
+    #[test]
+    fn padding_around_block_boundaries() {
+        // lengths where the final block has 55, 56, 63, 64 (and one more) bytes left
+        let vectors = [
+            (55, "ef1772b6dff9a122358552954ad0df65"),
+            (56, "3b0c8ac703f828b04c6c197006d17218"),
+            (63, "b06521f39153d618550606be297466d5"),
+            (64, "014842d480b571495a4a0363793f7367"),
+            (65, "c743a45e0d2e6a95cb859adae0248435"),
+        ];
+
+        for (len, expected) in vectors {
+            let input = "a".repeat(len);
+            assert_eq!(hex(md5(&input)), expected, "md5(\"a\" * {len})");
+        }
+    }
+
+    #[test]
+    fn incremental_matches_one_shot() {
+        let input = (0..=255u8).cycle().take(1000).collect::<Vec<_>>();
+
+        for split in [0, 1, 55, 56, 63, 64, 65, 128, 999, 1000] {
+            let mut hasher = Hasher::new();
+            hasher.update(&input[..split]);
+            hasher.update(&input[split..]);
+
+            assert_eq!(hasher.finalize(), md5(&input), "split at {split}");
+        }
+    }
+
+    #[test]
+    fn leading_zero_nibbles() {
+        let mut digest = Digest([0xff; 16]);
+        assert_eq!(digest.leading_zero_nibbles(), 0);
+
+        digest.0[..2].copy_from_slice(&[0x00, 0x0f]);
+        assert_eq!(digest.leading_zero_nibbles(), 3);
+
+        digest.0[1] = 0x00;
+        assert_eq!(digest.leading_zero_nibbles(), 4);
+    }
+}
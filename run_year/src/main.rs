@@ -0,0 +1,131 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::time::Duration;
+
+use aoc::budget::Outcome;
+
+/// get the days of `year_root`, sorted
+fn list_days(year_root: &Path) -> Vec<u32> {
+    let mut days = fs::read_dir(year_root)
+        .unwrap()
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| {
+            let dirname = entry.file_name().into_string().ok()?;
+            dirname.strip_prefix("day")?.parse::<u32>().ok()
+        })
+        .collect::<Vec<_>>();
+
+    days.sort_unstable();
+    days
+}
+
+/// pop the value after `flag`, if present, and parse it as seconds. an error if it isn't any
+fn pop_secs(args: &mut Vec<String>, flag: &str) -> Result<Option<Duration>, String> {
+    let Some(i) = args.iter().position(|arg| arg == flag) else {
+        return Ok(None);
+    };
+    let secs = args
+        .get(i + 1)
+        .and_then(|secs| secs.parse().ok())
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .ok_or_else(|| format!("{flag} takes a number of seconds"))?;
+    args.drain(i..i + 2);
+
+    Ok(Some(secs))
+}
+
+/// runs a day with a budget for each of its parts, printing what it prints.
+/// the outcomes of its parts, empty if the day doesn't support `--budget`
+fn run_day(year_root: &Path, day: u32, budget: Duration) -> Vec<(String, Outcome)> {
+    let binary = year_root
+        .join("target")
+        .join("release")
+        .join(format!("day{day}"));
+
+    let output = Command::new(binary)
+        .arg("--budget")
+        .arg(budget.as_secs_f64().to_string())
+        .stderr(Stdio::inherit())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    print!("{stdout}");
+
+    stdout
+        .lines()
+        .filter_map(Outcome::from_line)
+        .map(|(name, outcome)| (name.to_owned(), outcome))
+        .collect()
+}
+
+/// run every day of a year in release, and list the parts that don't fit the target.
+/// requires a `year`, optionally `--budget SECS` after which a part is given up on (default 10),
+/// and `--target SECS` (default 1)
+fn main() {
+    let mut args = env::args().skip(1).collect::<Vec<_>>();
+    let secs = |args: &mut Vec<String>, flag, default| {
+        pop_secs(args, flag)
+            .unwrap_or_else(|err| {
+                eprintln!("{err}");
+                process::exit(2);
+            })
+            .unwrap_or(Duration::from_secs(default))
+    };
+    let budget = secs(&mut args, "--budget", 10);
+    let target = secs(&mut args, "--target", 1);
+
+    let Some(year) = args.first().and_then(|year| year.parse::<u32>().ok()) else {
+        println!(
+            "Usage: cargo run --package={:?} -- year [--budget SECS] [--target SECS]",
+            env::current_exe().unwrap().file_stem().unwrap()
+        );
+        return;
+    };
+
+    let year_root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(year.to_string());
+
+    let built = Command::new("cargo")
+        .args(["build", "--release"])
+        .current_dir(&year_root)
+        .status()
+        .unwrap();
+    if !built.success() {
+        process::exit(1);
+    }
+
+    let mut slow = Vec::new();
+    let mut unbudgeted = Vec::new();
+
+    for day in list_days(&year_root) {
+        let parts = run_day(&year_root, day, budget);
+        if parts.is_empty() {
+            unbudgeted.push(format!("{year}/day{day}"));
+        }
+
+        slow.extend(parts.into_iter().filter(
+            |(_, outcome)| !matches!(outcome, Outcome::Done(elapsed) if *elapsed <= target),
+        ));
+    }
+
+    println!();
+    if slow.is_empty() {
+        println!(
+            "every part of {year} is within the {}s target",
+            target.as_secs_f64()
+        );
+    } else {
+        println!("over the {}s target:", target.as_secs_f64());
+        for (name, outcome) in &slow {
+            println!("  {name}: {outcome}");
+        }
+    }
+
+    if !unbudgeted.is_empty() {
+        println!("without a budget: {}", unbudgeted.join(", "));
+    }
+}
@@ -1,25 +1,5 @@
-#[allow(unused)]
-fn bench(desc: &str, workload: impl FnOnce()) {
-    use std::time::Instant;
-
-    let before = Instant::now();
-    workload();
-    println!("{desc}: {:.2?}", before.elapsed());
-}
-
-#[allow(unused)]
-fn debug<T: std::fmt::Debug>(ctx: T, delay: u64) {
-    if delay > 0 {
-        print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
-    }
-
-    print!("{ctx:?}");
-
-    if delay > 0 {
-        use std::{thread, time::Duration};
-        thread::sleep(Duration::from_millis(delay));
-    }
-}
+#[cfg(test)]
+mod examples;
 
 fn parse_input(input: &str) -> Vec<u32> {
     todo!()
@@ -37,9 +17,12 @@ fn main() {
     let input0 = include_str!("input0.txt");
     let input1 = include_str!("input1.txt");
 
-    println!("{:?}", part1(input0));
-    println!("{:?}", part1(input1));
-
-    println!("{:?}", part2(input0));
-    println!("{:?}", part2(input1));
-}
\ No newline at end of file
+    // to watch a simulation step by step: `aoc::anim::Animation::from_args()`, then `show` a frame per step
+    aoc::run::Day::new("YEAR/dayN")
+        .input("input0", input0)
+        .input("input1", input1)
+        .parse("parse", parse_input)
+        .part(1, part1)
+        .part(2, part2)
+        .run();
+}